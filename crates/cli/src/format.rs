@@ -4,15 +4,8 @@ use std::path::{Path, PathBuf};
 
 use bumpalo::Bump;
 use roc_error_macros::{internal_error, user_error};
-use roc_fmt::def::fmt_defs;
-use roc_fmt::module::fmt_module;
 use roc_fmt::spaces::RemoveSpaces;
-use roc_fmt::{Ast, Buf};
-use roc_parse::{
-    module::{self, module_defs},
-    parser::{Parser, SyntaxError},
-    state::State,
-};
+use roc_fmt::{format_module, Ast, Buf};
 
 #[derive(Copy, Clone, Debug)]
 pub enum FormatMode {
@@ -177,13 +170,13 @@ pub enum FormatProblem {
 }
 
 pub fn format_src(arena: &Bump, src: &str) -> Result<String, FormatProblem> {
-    let ast = arena.alloc(parse_all(arena, src).unwrap_or_else(|e| {
+    let ast = arena.alloc(Ast::parse(arena, src).unwrap_or_else(|e| {
         user_error!("Unexpected parse failure when parsing this formatting:\n\n{:?}\n\nParse error was:\n\n{:?}\n\n", src, e)
     }));
     let mut buf = Buf::new_in(arena);
-    fmt_all(&mut buf, ast);
+    format_module(&mut buf, ast);
 
-    let reparsed_ast = match arena.alloc(parse_all(arena, buf.as_str())) {
+    let reparsed_ast = match arena.alloc(Ast::parse(arena, buf.as_str())) {
         Ok(ast) => ast,
         Err(e) => {
             return Err(FormatProblem::ParsingFailed {
@@ -212,7 +205,7 @@ pub fn format_src(arena: &Bump, src: &str) -> Result<String, FormatProblem> {
     // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
     let mut reformatted_buf = Buf::new_in(arena);
 
-    fmt_all(&mut reformatted_buf, reparsed_ast);
+    format_module(&mut reformatted_buf, reparsed_ast);
 
     if buf.as_str() != reformatted_buf.as_str() {
         return Err(FormatProblem::ReformattingUnstable {
@@ -223,20 +216,3 @@ pub fn format_src(arena: &Bump, src: &str) -> Result<String, FormatProblem> {
 
     Ok(buf.as_str().to_string())
 }
-
-fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
-    let (module, state) = module::parse_header(arena, State::new(src.as_bytes()))
-        .map_err(|e| SyntaxError::Header(e.problem))?;
-
-    let (_, defs, _) = module_defs().parse(arena, state, 0).map_err(|(_, e)| e)?;
-
-    Ok(Ast { module, defs })
-}
-
-fn fmt_all<'a>(buf: &mut Buf<'a>, ast: &'a Ast) {
-    fmt_module(buf, &ast.module);
-
-    fmt_defs(buf, &ast.defs, 0);
-
-    buf.fmt_end_of_file();
-}
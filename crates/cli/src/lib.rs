@@ -68,6 +68,7 @@ pub const FLAG_STDIN: &str = "stdin";
 pub const FLAG_STDOUT: &str = "stdout";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
 pub const FLAG_OUTPUT: &str = "output";
+pub const FLAG_MESSAGE_FORMAT: &str = "message-format";
 pub const ROC_FILE: &str = "ROC_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_DIR: &str = "GLUE_DIR";
@@ -301,6 +302,14 @@ pub fn build_app() -> Command {
             .about("Check the code for problems, but don’t build or run it")
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(
+                Arg::new(FLAG_MESSAGE_FORMAT)
+                    .long(FLAG_MESSAGE_FORMAT)
+                    .help("How to report problems\n(With json-lines, each problem is printed as a JSON object on its own line, as soon as its module is checked. The .roc files to check are read from stdin, one per line, unless one is given as an argument.)")
+                    .value_parser(["human", "json-lines"])
+                    .required(false)
+                    .default_value("human"),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file of an app to check")
@@ -425,6 +434,8 @@ pub fn test(_matches: &ArgMatches, _triple: Triple) -> io::Result<i32> {
 #[cfg(not(windows))]
 pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     use roc_build::program::report_problems_monomorphized;
+    use roc_load::layering::LayerRules;
+    use roc_load::source_provider::RealFileSystem;
     use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError};
     use roc_packaging::cache;
     use roc_target::TargetInfo;
@@ -471,14 +482,15 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     let function_kind = FunctionKind::LambdaSet;
 
     // Step 1: compile the app and generate the .o file
+    let layer_rules = match LayerRules::for_root_module(&RealFileSystem, path) {
+        Ok(layer_rules) => layer_rules,
+        Err(problem) => return handle_loading_problem(problem),
+    };
+
     let load_config = LoadConfig {
-        target_info,
-        function_kind,
-        // TODO: expose this from CLI?
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
-        palette: roc_reporting::report::DEFAULT_PALETTE,
         threading,
-        exec_mode: ExecutionMode::Test,
+        layer_rules,
+        ..LoadConfig::new(target_info, function_kind, ExecutionMode::Test)
     };
     let load_result = roc_load::load_and_monomorphize(
         arena,
@@ -1,13 +1,14 @@
 //! The `roc` binary that brings together all functionality in the Roc toolset.
 use bumpalo::Bump;
+use clap::parser::ValueSource;
 use roc_build::link::LinkType;
-use roc_build::program::{check_file, CodeGenBackend};
+use roc_build::program::{check_file, check_files_json_lines, CodeGenBackend};
 use roc_cli::{
     build_app, format_files, format_src, test, BuildConfig, FormatMode, CMD_BUILD, CMD_CHECK,
     CMD_DEV, CMD_DOCS, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_PREPROCESS_HOST, CMD_REPL,
     CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_DEV, FLAG_LIB,
-    FLAG_NO_LINK, FLAG_OUTPUT, FLAG_STDIN, FLAG_STDOUT, FLAG_TARGET, FLAG_TIME, GLUE_DIR,
-    GLUE_SPEC, ROC_FILE,
+    FLAG_MESSAGE_FORMAT, FLAG_NO_LINK, FLAG_OUTPUT, FLAG_STDIN, FLAG_STDOUT, FLAG_TARGET,
+    FLAG_TIME, GLUE_DIR, GLUE_SPEC, ROC_FILE,
 };
 use roc_docs::generate_docs_html;
 use roc_error_macros::user_error;
@@ -202,51 +203,85 @@ fn main() -> io::Result<()> {
                 Some(n) => Threading::AtMost(*n),
             };
 
-            match check_file(
-                &arena,
-                roc_file_path.to_owned(),
-                emit_timings,
-                RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
-                threading,
-            ) {
-                Ok((problems, total_time)) => {
-                    println!(
-                        "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
-                        if problems.errors == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.errors,
-                        if problems.errors == 1 {
-                            "error"
-                        } else {
-                            "errors"
-                        },
-                        if problems.warnings == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.warnings,
-                        if problems.warnings == 1 {
-                            "warning"
-                        } else {
-                            "warnings"
-                        },
-                        total_time.as_millis(),
-                    );
+            let json_lines = matches
+                .get_one::<String>(FLAG_MESSAGE_FORMAT)
+                .map(String::as_str)
+                == Some("json-lines");
 
-                    Ok(problems.exit_code())
+            if json_lines {
+                if emit_timings {
+                    user_error!(
+                        "--{FLAG_TIME} can't be combined with --{FLAG_MESSAGE_FORMAT}=json-lines"
+                    );
                 }
 
-                Err(LoadingProblem::FormattedReport(report)) => {
-                    print!("{report}");
+                // a file given on the command line is the only root; otherwise they come on stdin
+                let roots: Box<dyn Iterator<Item = io::Result<PathBuf>>> =
+                    match matches.value_source(ROC_FILE) {
+                        Some(ValueSource::CommandLine) => {
+                            Box::new(std::iter::once(Ok(roc_file_path.to_owned())))
+                        }
+                        _ => Box::new(
+                            io::stdin()
+                                .lines()
+                                .map(|line| line.map(|line| PathBuf::from(line.trim()))),
+                        ),
+                    };
+
+                check_files_json_lines(
+                    roots,
+                    io::stdout(),
+                    RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+                    threading,
+                )
+                .map(|problems| problems.exit_code())
+            } else {
+                match check_file(
+                    &arena,
+                    roc_file_path.to_owned(),
+                    emit_timings,
+                    RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+                    threading,
+                ) {
+                    Ok((problems, total_time)) => {
+                        println!(
+                            "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
+                            if problems.errors == 0 {
+                                32 // green
+                            } else {
+                                33 // yellow
+                            },
+                            problems.errors,
+                            if problems.errors == 1 {
+                                "error"
+                            } else {
+                                "errors"
+                            },
+                            if problems.warnings == 0 {
+                                32 // green
+                            } else {
+                                33 // yellow
+                            },
+                            problems.warnings,
+                            if problems.warnings == 1 {
+                                "warning"
+                            } else {
+                                "warnings"
+                            },
+                            total_time.as_millis(),
+                        );
+
+                        Ok(problems.exit_code())
+                    }
 
-                    Ok(1)
-                }
-                Err(other) => {
-                    panic!("build_file failed with error:\n{other:?}");
+                    Err(LoadingProblem::FormattedReport(report)) => {
+                        print!("{report}");
+
+                        Ok(1)
+                    }
+                    Err(other) => {
+                        panic!("build_file failed with error:\n{other:?}");
+                    }
                 }
             }
         }
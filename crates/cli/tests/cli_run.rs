@@ -1373,6 +1373,9 @@ mod cli_run {
 
                 bar is listed as exposed, but it isn't defined in this module.
 
+                2│      exposes [bar]
+                                 ^^^
+
                 You can fix this by adding a definition for bar, or by removing it
                 from exposes.
 
@@ -1406,6 +1409,63 @@ mod cli_run {
         );
     }
 
+    #[test]
+    fn check_json_lines_reads_roots_from_stdin() {
+        let roots = [
+            known_bad_file("ExposedNotDefined.roc"),
+            known_bad_file("UnusedImport.roc"),
+        ];
+        let stdin: String = roots
+            .iter()
+            .map(|root| format!("{}\n", root.display()))
+            .collect();
+
+        let out = run_roc([CMD_CHECK, "--message-format=json-lines"], &[&stdin], &[]);
+
+        let diagnostics: Vec<serde_json::Value> = out
+            .stdout
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let found = |root: &Path, title: &str| {
+            diagnostics.iter().any(|diagnostic| {
+                diagnostic["root"] == root.to_str().unwrap() && diagnostic["title"] == title
+            })
+        };
+
+        assert!(found(&roots[0], "MISSING DEFINITION"), "{}", out.stdout);
+        assert!(found(&roots[1], "UNUSED IMPORT"), "{}", out.stdout);
+        assert_eq!(out.status.code(), Some(1), "{}", out.stderr);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn check_json_lines_with_non_utf8_path() {
+        use std::ffi::{OsStr, OsString};
+        use std::os::unix::ffi::OsStrExt;
+
+        let root = OsStr::from_bytes(b"does-not-exist/\xff.roc");
+        let out = run_roc(
+            [
+                OsString::from(CMD_CHECK),
+                OsString::from("--message-format=json-lines"),
+                root.to_os_string(),
+            ],
+            &[],
+            &[],
+        );
+
+        let diagnostics: Vec<serde_json::Value> = out
+            .stdout
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(diagnostics.len(), 1, "{}", out.stdout);
+        assert_eq!(diagnostics[0]["root"], root.to_string_lossy().as_ref());
+        assert_eq!(out.status.code(), Some(1), "{}", out.stderr);
+    }
+
     #[test]
     fn unknown_generates_with() {
         check_compile_error(
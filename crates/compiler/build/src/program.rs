@@ -7,19 +7,26 @@ use roc_error_macros::internal_error;
 use roc_gen_dev::AssemblyBackendMode;
 use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
+use roc_load::layering::LayerRules;
+use roc_load::progress::{ModuleProblems, ProgressListener};
+use roc_load::source_provider::RealFileSystem;
 use roc_load::{
-    EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadConfig, LoadMonomorphizedError,
-    LoadedModule, LoadingProblem, MonomorphizedModule, Threading,
+    import_chain, EntryPoint, ExecutionMode, ExpectMetadata, FunctionKind, LoadConfig,
+    LoadMonomorphizedError, LoadedModule, LoadingProblem, MonomorphizedModule, Threading,
 };
+use roc_module::symbol::ModuleId;
 use roc_mono::ir::{OptLevel, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
-use roc_reporting::{
-    cli::{report_problems, Problems},
-    report::{RenderTarget, DEFAULT_PALETTE},
+use roc_reporting::cli::{report_problems, Problems};
+use roc_reporting::report::{
+    to_file_problem_report, to_file_report_string, RenderTarget, DEFAULT_PALETTE,
 };
 use roc_target::{OperatingSystem, TargetInfo};
 use std::ffi::OsStr;
+use std::io::{self, IsTerminal, Write};
 use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::{
     path::{Path, PathBuf},
     thread::JoinHandle,
@@ -43,6 +50,7 @@ pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Proble
     report_problems(
         &loaded.sources,
         &loaded.interns,
+        |module_id| import_chain(&loaded.imported_by, module_id),
         &mut loaded.can_problems,
         &mut loaded.type_problems,
     )
@@ -52,6 +60,7 @@ pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
     report_problems(
         &loaded.sources,
         &loaded.interns,
+        |module_id| import_chain(&loaded.imported_by, module_id),
         &mut loaded.can_problems,
         &mut loaded.type_problems,
     )
@@ -706,12 +715,8 @@ pub fn standard_load_config(
     };
 
     LoadConfig {
-        target_info,
-        function_kind,
-        render: RenderTarget::ColorTerminal,
-        palette: DEFAULT_PALETTE,
         threading,
-        exec_mode,
+        ..LoadConfig::new(target_info, function_kind, exec_mode)
     }
 }
 
@@ -732,6 +737,13 @@ pub fn build_file<'a>(
 ) -> Result<BuiltFile<'a>, BuildFileError<'a>> {
     let compilation_start = Instant::now();
 
+    let load_config = LoadConfig {
+        layer_rules: LayerRules::for_root_module(&RealFileSystem, &app_module_path)
+            .map_err(BuildFileError::LoadingProblem)?,
+        progress: TerminalProgress::on_stderr(),
+        ..load_config
+    };
+
     // Step 1: compile the app and generate the .o file
     let loaded =
         roc_load::load_and_monomorphize(arena, app_module_path.clone(), roc_cache_dir, load_config)
@@ -1205,21 +1217,11 @@ pub fn check_file<'a>(
 ) -> Result<(Problems, Duration), LoadingProblem<'a>> {
     let compilation_start = Instant::now();
 
-    // only used for generating errors. We don't do code generation, so hardcoding should be fine
-    // we need monomorphization for when exhaustiveness checking
-    let target_info = TargetInfo::default_x86_64();
-
     // Step 1: compile the app and generate the .o file
 
     let load_config = LoadConfig {
-        target_info,
-        // TODO: we may not want this for just checking.
-        function_kind: FunctionKind::LambdaSet,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
-        palette: DEFAULT_PALETTE,
-        threading,
-        exec_mode: ExecutionMode::Check,
+        progress: TerminalProgress::on_stderr(),
+        ..check_load_config(&roc_file_path, threading)?
     };
     let mut loaded =
         roc_load::load_and_typecheck(arena, roc_file_path, roc_cache_dir, load_config)?;
@@ -1269,6 +1271,308 @@ pub fn check_file<'a>(
     Ok((report_problems_typechecked(&mut loaded), compilation_end))
 }
 
+/// Keeps a single line on stderr up to date with how many modules have been type-checked, and
+/// erases it once loading is done. Only used when stderr is a terminal.
+struct TerminalProgress {
+    discovered: AtomicUsize,
+    solved: AtomicUsize,
+}
+
+impl TerminalProgress {
+    fn on_stderr() -> Option<Arc<dyn ProgressListener>> {
+        if !io::stderr().is_terminal() {
+            return None;
+        }
+
+        Some(Arc::new(TerminalProgress {
+            discovered: AtomicUsize::new(0),
+            solved: AtomicUsize::new(0),
+        }))
+    }
+
+    fn draw(&self) {
+        let solved = self.solved.load(Ordering::Relaxed);
+        let discovered = self.discovered.load(Ordering::Relaxed);
+
+        eprint!("\r\x1B[2KChecked {solved} of {discovered} modules");
+    }
+}
+
+impl ProgressListener for TerminalProgress {
+    fn module_discovered(&self, _module_id: ModuleId, _path: &Path) {
+        self.discovered.fetch_add(1, Ordering::Relaxed);
+        self.draw();
+    }
+
+    fn module_solved(&self, _module_id: ModuleId) {
+        self.solved.fetch_add(1, Ordering::Relaxed);
+        self.draw();
+    }
+}
+
+impl Drop for TerminalProgress {
+    fn drop(&mut self) {
+        // the loader is done with us; leave the terminal as we found it
+        eprint!("\r\x1B[2K");
+    }
+}
+
+fn check_load_config<'a>(
+    roc_file_path: &Path,
+    threading: Threading,
+) -> Result<LoadConfig, LoadingProblem<'a>> {
+    // only used for generating errors. We don't do code generation, so hardcoding should be fine
+    // we need monomorphization for when exhaustiveness checking
+    let target_info = TargetInfo::default_x86_64();
+
+    Ok(LoadConfig {
+        threading,
+        layer_rules: LayerRules::for_root_module(&RealFileSystem, roc_file_path)?,
+        // TODO: we may not want FunctionKind::LambdaSet for just checking.
+        ..LoadConfig::new(
+            target_info,
+            FunctionKind::LambdaSet,
+            ExecutionMode::CheckProblemsOnly,
+        )
+    })
+}
+
+/// Check every root module in `roots`, writing each problem found to `out` as a single-line JSON
+/// object. This is the `roc check --message-format=json-lines` mode, meant for build systems
+/// that want diagnostics without parsing the human-readable output.
+///
+/// A module's problems are written as soon as it has been type-checked, so output starts long
+/// before a big root is done. Empty paths are skipped.
+pub fn check_files_json_lines<W: Write + Send + 'static>(
+    roots: impl IntoIterator<Item = io::Result<PathBuf>>,
+    out: W,
+    roc_cache_dir: RocCacheDir<'_>,
+    threading: Threading,
+) -> io::Result<Problems> {
+    let progress = Arc::new(JsonLinesProgress {
+        out: Mutex::new(JsonLinesOut {
+            writer: out,
+            root: PathBuf::new(),
+            problems: Problems::default(),
+            error: None,
+        }),
+    });
+
+    for root in roots {
+        let root = root?;
+
+        if root.as_os_str().is_empty() {
+            continue;
+        }
+
+        progress.lock().root = root.clone();
+
+        let arena = Bump::new();
+        let loaded = check_load_config(&root, threading).and_then(|load_config| {
+            let load_config = LoadConfig {
+                render: RenderTarget::Generic,
+                progress: Some(progress.clone() as Arc<dyn ProgressListener>),
+                ..load_config
+            };
+
+            roc_load::load_and_typecheck(&arena, root.clone(), roc_cache_dir, load_config)
+        });
+
+        // the problems of every module that was checked are already written
+        if let Err(problem) = loaded {
+            progress.lock().write_loading_problem(problem);
+        }
+
+        if let Some(error) = progress.lock().error.take() {
+            return Err(error);
+        }
+    }
+
+    let problems = progress.lock().problems;
+
+    Ok(problems)
+}
+
+/// Writes the problems of each module as JSON lines, as soon as the module is type-checked.
+struct JsonLinesProgress<W> {
+    out: Mutex<JsonLinesOut<W>>,
+}
+
+struct JsonLinesOut<W> {
+    writer: W,
+    /// The root module being checked
+    root: PathBuf,
+    /// Of every root checked so far
+    problems: Problems,
+    /// The first write that failed. The loader can't be told to stop, so this is returned once
+    /// the current root is done.
+    error: Option<io::Error>,
+}
+
+impl<W> JsonLinesProgress<W> {
+    fn lock(&self) -> MutexGuard<'_, JsonLinesOut<W>> {
+        self.out
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<W: Write + Send> ProgressListener for JsonLinesProgress<W> {
+    fn wants_problems(&self) -> bool {
+        true
+    }
+
+    fn module_checked(&self, module: ModuleProblems<'_>) {
+        self.lock().write_module_problems(module);
+    }
+}
+
+impl<W: Write> JsonLinesOut<W> {
+    /// Writes and flushes one line, so that whoever reads `writer` sees it right away
+    fn write_line(&mut self, diagnostic: serde_json::Value) {
+        if self.error.is_none() {
+            let written = writeln!(self.writer, "{diagnostic}").and_then(|()| self.writer.flush());
+
+            if let Err(error) = written {
+                self.error = Some(error);
+            }
+        }
+    }
+
+    fn write_module_problems(&mut self, module: ModuleProblems<'_>) {
+        use roc_problem::Severity;
+        use roc_region::all::LineInfo;
+        use roc_reporting::report::{can_problem, type_problem, RocDocAllocator};
+
+        let ModuleProblems {
+            module_id,
+            path,
+            src,
+            can_problems,
+            type_problems,
+            interns,
+            imported_by,
+        } = module;
+
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let line_info = LineInfo::new(src);
+        let alloc = RocDocAllocator::new(&src_lines, module_id, interns);
+        let imported_by: Vec<&str> = import_chain(imported_by, module_id)
+            .into_iter()
+            .map(|module_id| interns.module_name(module_id).as_str())
+            .collect();
+
+        let can_reports = can_problems.iter().map(|problem| {
+            let region = problem.region();
+            let report = can_problem(&alloc, &line_info, path.to_path_buf(), problem.clone());
+
+            (region, Some(report))
+        });
+
+        let type_reports = type_problems.iter().map(|problem| {
+            let region = problem.region();
+            let report = type_problem(&alloc, &line_info, path.to_path_buf(), problem.clone());
+
+            (region, report)
+        });
+
+        for (region, report) in can_reports.chain(type_reports) {
+            let Some(report) = report else {
+                continue;
+            };
+
+            let severity = match report.severity {
+                Severity::Warning => {
+                    self.problems.warnings += 1;
+                    "warning"
+                }
+                Severity::RuntimeError => {
+                    self.problems.errors += 1;
+                    "error"
+                }
+                Severity::Fatal => {
+                    self.problems.fatally_errored = true;
+                    self.problems.errors += 1;
+                    "fatal"
+                }
+            };
+
+            let title = report.title.clone();
+            let mut message = String::new();
+            report.render_ci(&mut message, &alloc);
+
+            // json! panics on a path that isn't UTF-8
+            let mut diagnostic = serde_json::json!({
+                "root": self.root.to_string_lossy(),
+                "file": path.to_string_lossy(),
+                "severity": severity,
+                "title": title,
+                "message": message,
+                "imported_by": imported_by,
+            });
+
+            if let Some(region) = region {
+                let region = line_info.convert_region(region);
+
+                // 1-based, like the line numbers in the human-readable reports
+                diagnostic["start"] = serde_json::json!({
+                    "line": region.start().line + 1,
+                    "column": region.start().column + 1,
+                });
+                diagnostic["end"] = serde_json::json!({
+                    "line": region.end().line + 1,
+                    "column": region.end().column + 1,
+                });
+            }
+
+            self.write_line(diagnostic);
+        }
+    }
+
+    /// A problem that stopped the root from being checked at all
+    fn write_loading_problem(&mut self, problem: LoadingProblem) {
+        let (title, message) = match problem {
+            LoadingProblem::FormattedReport(report) => ("LOADING FAILED", report),
+            LoadingProblem::FileProblem { filename, error } => (
+                "FILE PROBLEM",
+                to_file_report_string(RenderTarget::Generic, &DEFAULT_PALETTE, |alloc| {
+                    to_file_problem_report(alloc, &filename, error)
+                }),
+            ),
+            LoadingProblem::CouldNotFindCacheDir => (
+                "CACHE DIRECTORY NOT FOUND",
+                "I could not find a directory to cache downloaded packages in.".to_string(),
+            ),
+            LoadingProblem::UnexpectedHeader(header) => (
+                "UNEXPECTED HEADER",
+                format!("I did not expect a {header} header here."),
+            ),
+            LoadingProblem::ErrJoiningWorkerThreads | LoadingProblem::ChannelProblem(_) => (
+                "COMPILER BUG",
+                "The compiler's worker threads stopped unexpectedly.".to_string(),
+            ),
+            // the loader renders the rest into a report before it gets here
+            _ => (
+                "LOADING FAILED",
+                "I could not load this module, or one of the modules it imports.".to_string(),
+            ),
+        };
+
+        self.problems.fatally_errored = true;
+        self.problems.errors += 1;
+
+        let diagnostic = serde_json::json!({
+            "root": self.root.to_string_lossy(),
+            "file": self.root.to_string_lossy(),
+            "severity": "fatal",
+            "title": title,
+            "message": message,
+        });
+
+        self.write_line(diagnostic);
+    }
+}
+
 pub fn build_str_test<'a>(
     arena: &'a Bump,
     app_module_path: &Path,
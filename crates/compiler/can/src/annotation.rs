@@ -381,13 +381,20 @@ pub(crate) fn make_apply_symbol(
     scope: &mut Scope,
     module_name: &str,
     ident: &str,
+    type_arguments: &[Loc<TypeAnnotation>],
 ) -> Result<Symbol, Type> {
+    let name_region = apply_name_region(region, module_name, ident, type_arguments);
+
     if module_name.is_empty() {
         // Since module_name was empty, this is an unqualified type.
         // Look it up in scope!
 
         match scope.lookup_str(ident, region) {
-            Ok(symbol) => Ok(symbol),
+            Ok(symbol) => {
+                env.occurrences.push(Loc::at(name_region, symbol));
+
+                Ok(symbol)
+            }
             Err(problem) => {
                 env.problem(roc_problem::can::Problem::RuntimeError(problem));
 
@@ -396,7 +403,11 @@ pub(crate) fn make_apply_symbol(
         }
     } else {
         match env.qualified_lookup(scope, module_name, ident, region) {
-            Ok(symbol) => Ok(symbol),
+            Ok(symbol) => {
+                env.occurrences.push(Loc::at(name_region, symbol));
+
+                Ok(symbol)
+            }
             Err(problem) => {
                 // Either the module wasn't imported, or
                 // it was imported but it doesn't expose this ident.
@@ -410,6 +421,29 @@ pub(crate) fn make_apply_symbol(
     }
 }
 
+/// The region of just the name in an applied type like `List Str`, whose `region` also covers
+/// the type arguments. Without arguments the two are the same. With arguments, the spaces and
+/// comments before them are not part of the name, so it is measured from the start instead;
+/// a (qualified) type name never contains whitespace itself.
+fn apply_name_region(
+    region: Region,
+    module_name: &str,
+    ident: &str,
+    type_arguments: &[Loc<TypeAnnotation>],
+) -> Region {
+    if type_arguments.is_empty() {
+        return region;
+    }
+
+    let name_len = if module_name.is_empty() {
+        ident.len()
+    } else {
+        module_name.len() + 1 + ident.len()
+    };
+
+    Region::new(region.start(), region.start().bump_column(name_len as u32))
+}
+
 /// Retrieves all symbols in an annotations that reference a type definition, that is either an
 /// alias or an opaque type.
 ///
@@ -580,10 +614,11 @@ fn can_annotation_help(
             Type::Function(args, Box::new(closure), Box::new(ret))
         }
         Apply(module_name, ident, type_arguments) => {
-            let symbol = match make_apply_symbol(env, region, scope, module_name, ident) {
-                Err(problem) => return problem,
-                Ok(symbol) => symbol,
-            };
+            let symbol =
+                match make_apply_symbol(env, region, scope, module_name, ident, type_arguments) {
+                    Err(problem) => return problem,
+                    Ok(symbol) => symbol,
+                };
 
             let mut args = Vec::new();
 
@@ -1077,8 +1112,9 @@ fn canonicalize_has_clause(
     } in *abilities
     {
         let ability = match ability {
-            TypeAnnotation::Apply(module_name, ident, _type_arguments) => {
-                let symbol = make_apply_symbol(env, region, scope, module_name, ident)?;
+            TypeAnnotation::Apply(module_name, ident, type_arguments) => {
+                let symbol =
+                    make_apply_symbol(env, region, scope, module_name, ident, type_arguments)?;
 
                 // Ability defined locally, whose members we are constructing right now...
                 if !pending_abilities_in_scope.contains_key(&symbol)
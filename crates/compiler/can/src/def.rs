@@ -721,7 +721,7 @@ fn canonicalize_opaque<'a>(
             // Op := {} has [Eq]
             let (ability, members) = match ability.value {
                 ast::TypeAnnotation::Apply(module_name, ident, []) => {
-                    match make_apply_symbol(env, region, scope, module_name, ident) {
+                    match make_apply_symbol(env, ability_region, scope, module_name, ident, &[]) {
                         Ok(ability) => {
                             let opt_members = scope
                                 .abilities_store
@@ -1052,7 +1052,6 @@ fn canonicalize_value_defs<'a>(
                 // that get would have gotten added later in the defs list!
                 pending_value_defs.push(pending_def);
             }
-            PendingValue::SignatureDefMismatch => { /* skip */ }
             PendingValue::Dbg(pending_dbg) => {
                 pending_dbgs.push(pending_dbg);
             }
@@ -2750,7 +2749,6 @@ enum PendingValue<'a> {
     Dbg(PendingExpectOrDbg<'a>),
     Expect(PendingExpectOrDbg<'a>),
     ExpectFx(PendingExpectOrDbg<'a>),
-    SignatureDefMismatch,
 }
 
 struct PendingExpectOrDbg<'a> {
@@ -2758,6 +2756,17 @@ struct PendingExpectOrDbg<'a> {
     preceding_comment: Region,
 }
 
+/// The name a def pattern binds, if it is a plain identifier
+fn pattern_identifier(pattern: &ast::Pattern<'_>) -> Option<Ident> {
+    match pattern {
+        ast::Pattern::Identifier(ident) => Some(Ident::from(*ident)),
+        ast::Pattern::SpaceBefore(inner, _) | ast::Pattern::SpaceAfter(inner, _) => {
+            pattern_identifier(inner)
+        }
+        _ => None,
+    }
+}
+
 fn to_pending_value_def<'a>(
     env: &mut Env<'a>,
     var_store: &mut VarStore,
@@ -2831,6 +2840,11 @@ fn to_pending_value_def<'a>(
                     body_pattern.region,
                 );
 
+                // the signature names the def as well
+                if let Pattern::Identifier(symbol) = loc_can_pattern.value {
+                    env.bindings.push(Loc::at(ann_pattern.region, symbol));
+                }
+
                 PendingValue::Def(PendingValueDef::TypedBody(
                     body_pattern,
                     loc_can_pattern,
@@ -2838,16 +2852,29 @@ fn to_pending_value_def<'a>(
                     body_expr,
                 ))
             } else {
-                // the pattern of the annotation does not match the pattern of the body direc
+                // the pattern of the annotation does not match the pattern of the body directly
+                // below it, which is most likely a typo in one of the names
                 env.problems.push(Problem::SignatureDefMismatch {
                     annotation_pattern: ann_pattern.region,
                     def_pattern: body_pattern.region,
+                    annotation_name: pattern_identifier(&ann_pattern.value),
+                    def_name: pattern_identifier(&body_pattern.value),
                 });
 
-                // TODO: Should we instead build some PendingValueDef::InvalidAnnotatedBody ? This would
-                // remove the `Option` on this function (and be probably more reliable for further
-                // problem/error reporting)
-                PendingValue::SignatureDefMismatch
+                // Still define the body, just without the annotation. Otherwise every use of it
+                // would be reported as well, burying the actual problem.
+                let loc_can_pattern = canonicalize_def_header_pattern(
+                    env,
+                    var_store,
+                    scope,
+                    pending_abilities_in_scope,
+                    output,
+                    pattern_type,
+                    &body_pattern.value,
+                    body_pattern.region,
+                );
+
+                PendingValue::Def(PendingValueDef::Body(loc_can_pattern, body_expr))
             }
         }
 
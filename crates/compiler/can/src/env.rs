@@ -1,11 +1,28 @@
 use crate::procedure::References;
 use crate::scope::Scope;
 use bumpalo::Bump;
-use roc_collections::{MutMap, VecSet};
+use roc_collections::{MutMap, VecMap, VecSet};
 use roc_module::ident::{Ident, Lowercase, ModuleName};
 use roc_module::symbol::{IdentIdsByModule, ModuleId, ModuleIds, Symbol};
-use roc_problem::can::{Problem, RuntimeError};
+use roc_problem::can::{ImportCandidate, Problem, RuntimeError};
 use roc_region::all::{Loc, Region};
+use std::sync::Arc;
+
+const MAX_QUALIFIED_SUGGESTIONS: usize = 3;
+
+/// What each module exposes, as far as the modules that might import it are concerned.
+/// Shared, so every importer can get a copy without cloning the entries themselves.
+pub type ExposedValues = MutMap<ModuleId, Arc<ModuleExposes>>;
+
+#[derive(Debug, Clone, Default)]
+pub struct ModuleExposes {
+    /// The exposed values, by name. Used to suggest a value from another module when a name is
+    /// not in scope.
+    pub values: Vec<(Ident, Symbol)>,
+    /// The exposed values and types whose doc comment marks them as deprecated, with the
+    /// message to show wherever another module uses them
+    pub deprecated: VecMap<Symbol, Box<str>>,
+}
 
 /// The canonicalization environment for a particular module.
 pub struct Env<'a> {
@@ -15,6 +32,8 @@ pub struct Env<'a> {
 
     pub dep_idents: &'a IdentIdsByModule,
 
+    pub exposed_values: &'a ExposedValues,
+
     pub module_ids: &'a ModuleIds,
 
     /// Problems we've encountered along the way, which will be reported to the user at the end.
@@ -34,6 +53,15 @@ pub struct Env<'a> {
 
     pub top_level_symbols: VecSet<Symbol>,
 
+    /// Every place a value, type or opaque was referred to by name, in the order they were
+    /// canonicalized. Definitions are not included.
+    pub occurrences: Vec<Loc<Symbol>>,
+
+    /// Every place a pattern binds a name: defs at any depth, function arguments, and the names
+    /// bound by destructures and `when` branches. Together with `occurrences`, this is every
+    /// place a value is named.
+    pub bindings: Vec<Loc<Symbol>>,
+
     pub arena: &'a Bump,
 }
 
@@ -42,12 +70,14 @@ impl<'a> Env<'a> {
         arena: &'a Bump,
         home: ModuleId,
         dep_idents: &'a IdentIdsByModule,
+        exposed_values: &'a ExposedValues,
         module_ids: &'a ModuleIds,
     ) -> Env<'a> {
         Env {
             arena,
             home,
             dep_idents,
+            exposed_values,
             module_ids,
             problems: Vec::new(),
             closures: MutMap::default(),
@@ -55,6 +85,8 @@ impl<'a> Env<'a> {
             qualified_type_lookups: VecSet::default(),
             tailcallable_symbol: None,
             top_level_symbols: VecSet::default(),
+            occurrences: Vec::new(),
+            bindings: Vec::new(),
         }
     }
 
@@ -134,6 +166,8 @@ impl<'a> Env<'a> {
                             .ident_strs()
                             .map(|(_, string)| string.into())
                             .collect(),
+                        qualified_suggestions: Vec::new(),
+                        import_candidate: None,
                         underscored_suggestion_region: None,
                     };
                     Err(error)
@@ -190,6 +224,58 @@ impl<'a> Env<'a> {
         }
     }
 
+    /// Values that imported modules expose under a name close to `ident`, closest first, like
+    /// `Bool.true` for `true`. These are worth suggesting when `ident` isn't in scope.
+    pub fn similar_exposed_values(&self, ident: &str) -> Vec<Box<str>> {
+        // Short names only match exactly; anything else is mostly noise
+        let max_distance = ident.len() / 4;
+
+        let mut similar: Vec<(usize, Box<str>)> = Vec::new();
+
+        for module_id in self.dep_idents.keys().filter(|id| **id != self.home) {
+            let (Some(module_name), Some(exposes)) = (
+                self.module_ids.get_name(*module_id),
+                self.exposed_values.get(module_id),
+            ) else {
+                continue;
+            };
+
+            for (name, _) in &exposes.values {
+                let distance = distance::damerau_levenshtein(ident, name.as_str());
+
+                if distance <= max_distance {
+                    similar.push((distance, format!("{module_name}.{name}").into()));
+                }
+            }
+        }
+
+        // by name too, so the order doesn't depend on how the modules are hashed
+        similar.sort();
+        similar.truncate(MAX_QUALIFIED_SUGGESTIONS);
+
+        similar.into_iter().map(|(_, name)| name).collect()
+    }
+
+    /// The value named `ident`, if exactly one module this one imports, directly or not,
+    /// exposes a value with that name.
+    pub fn only_exposed_value_named(&self, ident: &str) -> Option<ImportCandidate> {
+        let mut found = self
+            .exposed_values
+            .iter()
+            .filter(|(module_id, _)| **module_id != self.home)
+            .flat_map(|(_, exposes)| &exposes.values)
+            .filter(|(name, _)| name.as_str() == ident)
+            .map(|(_, symbol)| ImportCandidate {
+                symbol: *symbol,
+                imported: self.dep_idents.contains_key(&symbol.module_id()),
+            });
+
+        match (found.next(), found.next()) {
+            (Some(candidate), None) => Some(candidate),
+            _ => None,
+        }
+    }
+
     pub fn problem(&mut self, problem: Problem) {
         self.problems.push(problem)
     }
@@ -883,6 +883,7 @@ pub fn canonicalize_expr<'a>(
                         Ok((name, opaque_def)) => {
                             let argument = Box::new(args.pop().unwrap());
                             output.references.insert_type_lookup(name);
+                            env.occurrences.push(Loc::at(loc_fn.region, name));
 
                             let (type_arguments, lambda_set_variables, specialized_def_type) =
                                 freshen_opaque_def(var_store, opaque_def);
@@ -1194,6 +1195,7 @@ pub fn canonicalize_expr<'a>(
                 Ok((name, opaque_def)) => {
                     let mut output = Output::default();
                     output.references.insert_type_lookup(name);
+                    env.occurrences.push(Loc::at(region, name));
 
                     let (type_arguments, lambda_set_variables, specialized_def_type) =
                         freshen_opaque_def(var_store, opaque_def);
@@ -1878,6 +1880,7 @@ fn canonicalize_var_lookup(
         match scope.lookup_str(ident, region) {
             Ok(symbol) => {
                 output.references.insert_value_lookup(symbol);
+                env.occurrences.push(Loc::at(region, symbol));
 
                 if scope.abilities_store.is_ability_member_name(symbol) {
                     AbilityMember(
@@ -1889,7 +1892,17 @@ fn canonicalize_var_lookup(
                     Var(symbol, var_store.fresh())
                 }
             }
-            Err(problem) => {
+            Err(mut problem) => {
+                if let roc_problem::can::RuntimeError::LookupNotInScope {
+                    qualified_suggestions,
+                    import_candidate,
+                    ..
+                } = &mut problem
+                {
+                    *qualified_suggestions = env.similar_exposed_values(ident);
+                    *import_candidate = env.only_exposed_value_named(ident);
+                }
+
                 env.problem(Problem::RuntimeError(problem.clone()));
 
                 RuntimeError(problem)
@@ -1901,6 +1914,7 @@ fn canonicalize_var_lookup(
         match env.qualified_lookup(scope, module_name, ident, region) {
             Ok(symbol) => {
                 output.references.insert_value_lookup(symbol);
+                env.occurrences.push(Loc::at(region, symbol));
 
                 if scope.abilities_store.is_ability_member_name(symbol) {
                     AbilityMember(
@@ -2,7 +2,7 @@ use crate::abilities::{AbilitiesStore, ImplKey, PendingAbilitiesStore, ResolvedI
 use crate::annotation::{canonicalize_annotation, AnnotationFor};
 use crate::def::{canonicalize_defs, Def};
 use crate::effect_module::HostedGeneratedFunctions;
-use crate::env::Env;
+use crate::env::{Env, ExposedValues};
 use crate::expr::{
     ClosureData, DbgLookup, Declarations, ExpectLookup, Expr, Output, PendingDerives,
 };
@@ -15,9 +15,9 @@ use roc_module::ident::Ident;
 use roc_module::ident::Lowercase;
 use roc_module::symbol::{IdentIds, IdentIdsByModule, ModuleId, ModuleIds, Symbol};
 use roc_parse::ast::{Defs, TypeAnnotation};
-use roc_parse::header::HeaderType;
+use roc_parse::header::{ExposedName, HeaderType};
 use roc_parse::pattern::PatternType;
-use roc_problem::can::{Problem, RuntimeError};
+use roc_problem::can::{Problem, RuntimeError, ShadowKind};
 use roc_region::all::{Loc, Region};
 use roc_types::subs::{ExposedTypesStorageSubs, Subs, VarStore, Variable};
 use roc_types::types::{AbilitySet, Alias, AliasKind, AliasVar, Type};
@@ -158,6 +158,10 @@ pub struct ModuleOutput {
     pub scope: Scope,
     pub loc_expects: VecMap<Region, Vec<ExpectLookup>>,
     pub loc_dbgs: VecMap<Symbol, DbgLookup>,
+    /// Every place a symbol is referred to by name; see [crate::env::Env::occurrences]
+    pub occurrences: Vec<Loc<Symbol>>,
+    /// Every place a pattern binds a name; see [crate::env::Env::bindings]
+    pub bindings: Vec<Loc<Symbol>>,
 }
 
 fn validate_generate_with<'a>(
@@ -268,6 +272,28 @@ fn has_no_implementation(expr: &Expr) -> bool {
     }
 }
 
+/// Where the header lists `name` among the values it exposes (or provides)
+fn exposed_region(header_type: &HeaderType, name: &str) -> Region {
+    use HeaderType::*;
+
+    let exposed: &[Loc<ExposedName>] = match header_type {
+        App { provides, .. } => provides,
+        Hosted { exposes, .. } | Builtin { exposes, .. } | Interface { exposes, .. } => exposes,
+        Platform { provides, .. } => {
+            return provides
+                .iter()
+                .find(|(loc_name, _)| loc_name.value.as_str() == name)
+                .map_or(Region::zero(), |(loc_name, _)| loc_name.region);
+        }
+        Package { .. } => &[],
+    };
+
+    exposed
+        .iter()
+        .find(|loc_name| loc_name.value.as_str() == name)
+        .map_or(Region::zero(), |loc_name| loc_name.region)
+}
+
 // TODO trim these down
 #[allow(clippy::too_many_arguments)]
 pub fn canonicalize_module_defs<'a>(
@@ -280,6 +306,7 @@ pub fn canonicalize_module_defs<'a>(
     module_ids: &'a ModuleIds,
     exposed_ident_ids: IdentIds,
     dep_idents: &'a IdentIdsByModule,
+    exposed_values: &'a ExposedValues,
     aliases: MutMap<Symbol, Alias>,
     imported_abilities_state: PendingAbilitiesStore,
     exposed_imports: MutMap<Ident, (Symbol, Region)>,
@@ -289,7 +316,7 @@ pub fn canonicalize_module_defs<'a>(
 ) -> ModuleOutput {
     let mut can_exposed_imports = MutMap::default();
     let mut scope = Scope::new(home, exposed_ident_ids, imported_abilities_state);
-    let mut env = Env::new(arena, home, dep_idents, module_ids);
+    let mut env = Env::new(arena, home, dep_idents, exposed_values, module_ids);
 
     for (name, alias) in aliases.into_iter() {
         scope.add_alias(
@@ -331,7 +358,7 @@ pub fn canonicalize_module_defs<'a>(
         let first_char = ident.as_inline_str().as_str().chars().next().unwrap();
 
         if first_char.is_lowercase() {
-            match scope.import(ident, symbol, region) {
+            match scope.import(ident.clone(), symbol, region) {
                 Ok(()) => {
                     // Add an entry to exposed_imports using the current module's name
                     // as the key; e.g. if this is the Foo module and we have
@@ -339,8 +366,12 @@ pub fn canonicalize_module_defs<'a>(
                     // anything references `baz` in this Foo module, it will resolve to Bar.baz.
                     can_exposed_imports.insert(symbol, region);
                 }
-                Err((_shadowed_symbol, _region)) => {
-                    internal_error!("TODO gracefully handle shadowing in imports.")
+                Err((_shadowed_symbol, original_region)) => {
+                    env.problem(Problem::Shadowing {
+                        original_region,
+                        shadow: Loc::at(region, ident),
+                        kind: ShadowKind::Variable,
+                    });
                 }
             }
         } else {
@@ -354,15 +385,27 @@ pub fn canonicalize_module_defs<'a>(
 
             // but now we know this symbol by a different identifier, so we still need to add it to
             // the scope
-            match scope.import(ident, symbol, region) {
+            match scope.import(ident.clone(), symbol, region) {
                 Ok(()) => {
                     // here we do nothing special
                 }
-                Err((shadowed_symbol, _region)) => {
-                    internal_error!(
-                        "TODO gracefully handle shadowing in imports, {:?} is shadowed.",
-                        shadowed_symbol
-                    )
+                Err((shadowed_symbol, original_region)) => {
+                    let kind = if scope.abilities_store.is_ability(shadowed_symbol) {
+                        ShadowKind::Ability(shadowed_symbol)
+                    } else {
+                        match scope.lookup_alias(shadowed_symbol) {
+                            Some(alias) if alias.kind == AliasKind::Opaque => {
+                                ShadowKind::Opaque(shadowed_symbol)
+                            }
+                            _ => ShadowKind::Alias(shadowed_symbol),
+                        }
+                    };
+
+                    env.problem(Problem::Shadowing {
+                        original_region,
+                        shadow: Loc::at(region, ident),
+                        kind,
+                    });
                 }
             }
         }
@@ -678,7 +721,12 @@ pub fn canonicalize_module_defs<'a>(
     // not, that means they were declared as exposed but there was
     // no actual declaration with that name!
     for symbol in exposed_but_not_defined {
-        env.problem(Problem::ExposedButNotDefined(symbol));
+        let name = scope.locals.ident_ids.get_name(symbol.ident_id()).unwrap();
+
+        env.problem(Problem::ExposedButNotDefined(
+            symbol,
+            exposed_region(header_type, name),
+        ));
 
         // In case this exposed value is referenced by other modules,
         // create a decl for it whose implementation is a runtime error.
@@ -805,6 +853,8 @@ pub fn canonicalize_module_defs<'a>(
         referenced_types,
         exposed_imports: can_exposed_imports,
         problems: env.problems,
+        occurrences: env.occurrences,
+        bindings: env.bindings,
         symbols_from_requires,
         pending_derives,
         loc_expects: collected.expects,
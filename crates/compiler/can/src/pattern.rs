@@ -272,6 +272,8 @@ pub fn canonicalize_def_header_pattern<'a>(
                 region,
             ) {
                 Ok((symbol, shadowing_ability_member)) => {
+                    env.bindings.push(Loc::at(region, symbol));
+
                     let can_pattern = match shadowing_ability_member {
                         // A fresh identifier.
                         None => {
@@ -289,15 +291,15 @@ pub fn canonicalize_def_header_pattern<'a>(
                     };
                     Loc::at(region, can_pattern)
                 }
-                Err((original_region, shadow, new_symbol)) => {
+                Err((shadowed_symbol, shadow, new_symbol)) => {
                     env.problem(Problem::RuntimeError(RuntimeError::Shadowing {
-                        original_region,
+                        original_region: shadowed_symbol.region,
                         shadow: shadow.clone(),
-                        kind: ShadowKind::Variable,
+                        kind: variable_shadow_kind(shadowed_symbol.value),
                     }));
                     output.references.insert_bound(new_symbol);
 
-                    let can_pattern = Pattern::Shadowed(original_region, shadow, new_symbol);
+                    let can_pattern = Pattern::Shadowed(shadowed_symbol.region, shadow, new_symbol);
                     Loc::at(region, can_pattern)
                 }
             }
@@ -321,6 +323,16 @@ pub fn canonicalize_def_header_pattern<'a>(
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub struct PermitShadows(pub bool);
 
+/// Shadowing a builtin that the module imports by name, like `concat` from
+/// `imports [List.{ concat }]`, gets a report that names the builtin.
+fn variable_shadow_kind(shadowed_symbol: Symbol) -> ShadowKind {
+    if shadowed_symbol.is_builtin() {
+        ShadowKind::BuiltinImport(shadowed_symbol)
+    } else {
+        ShadowKind::Variable
+    }
+}
+
 fn canonicalize_pattern_symbol(
     env: &mut Env,
     scope: &mut Scope,
@@ -344,7 +356,7 @@ fn canonicalize_pattern_symbol(
                 env.problem(Problem::RuntimeError(RuntimeError::Shadowing {
                     original_region: shadowed_symbol.region,
                     shadow: shadow.clone(),
-                    kind: ShadowKind::Variable,
+                    kind: variable_shadow_kind(shadowed_symbol.value),
                 }));
                 output.references.insert_bound(new_symbol);
 
@@ -375,7 +387,11 @@ pub fn canonicalize_pattern<'a>(
     let can_pattern = match pattern {
         Identifier(name) => {
             match canonicalize_pattern_symbol(env, scope, output, region, permit_shadows, name) {
-                Ok(symbol) => Pattern::Identifier(symbol),
+                Ok(symbol) => {
+                    env.bindings.push(Loc::at(region, symbol));
+
+                    Pattern::Identifier(symbol)
+                }
                 Err(pattern) => pattern,
             }
         }
@@ -632,6 +648,7 @@ pub fn canonicalize_pattern<'a>(
                         match scope.introduce(label.into(), region) {
                             Ok(symbol) => {
                                 output.references.insert_bound(symbol);
+                                env.bindings.push(Loc::at(loc_pattern.region, symbol));
 
                                 destructs.push(Loc {
                                     region: loc_pattern.region,
@@ -703,6 +720,12 @@ pub fn canonicalize_pattern<'a>(
                                 // an optional field binds the symbol!
                                 output.references.insert_bound(symbol);
 
+                                // the field's region also covers its default, like `x ? 0`
+                                let start = loc_pattern.region.start();
+                                let label_region =
+                                    Region::new(start, start.bump_column(label.len() as u32));
+                                env.bindings.push(Loc::at(label_region, symbol));
+
                                 output.union(expr_output);
 
                                 destructs.push(Loc {
@@ -790,6 +813,8 @@ pub fn canonicalize_pattern<'a>(
                                     pattern_as.identifier.value,
                                 ) {
                                     Ok(symbol) => {
+                                        env.bindings
+                                            .push(Loc::at(pattern_as.identifier.region, symbol));
                                         rest_name = Some(symbol);
                                     }
                                     Err(pattern) => {
@@ -863,7 +888,12 @@ pub fn canonicalize_pattern<'a>(
                 permit_shadows,
                 pattern_as.identifier.value,
             ) {
-                Ok(symbol) => Pattern::As(Box::new(can_subpattern), symbol),
+                Ok(symbol) => {
+                    env.bindings
+                        .push(Loc::at(pattern_as.identifier.region, symbol));
+
+                    Pattern::As(Box::new(can_subpattern), symbol)
+                }
                 Err(pattern) => pattern,
             }
         }
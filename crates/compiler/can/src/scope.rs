@@ -102,6 +102,8 @@ impl Scope {
                         value: Ident::from(ident),
                     },
                     suggestion_options: self.idents_in_scope().map(|v| v.as_ref().into()).collect(),
+                    qualified_suggestions: Vec::new(),
+                    import_candidate: None,
                     // Check if the user just forgot to remove an underscore from an ignored identifier
                     underscored_suggestion_region: self.lookup_ignored_local(ident),
                 };
@@ -320,7 +322,7 @@ impl Scope {
         pending_abilities_in_scope: &PendingAbilitiesInScope,
         ident: Ident,
         region: Region,
-    ) -> Result<(Symbol, Option<Symbol>), (Region, Loc<Ident>, Symbol)> {
+    ) -> Result<(Symbol, Option<Symbol>), (Loc<Symbol>, Loc<Ident>, Symbol)> {
         let ident = &ident;
 
         match self.introduce_help(ident.as_str(), region) {
@@ -339,7 +341,7 @@ impl Scope {
                                 value: ident.clone(),
                                 region,
                             };
-                            Err((loc_original_shadow.region, shadow, shadow_symbol))
+                            Err((*loc_original_shadow, shadow, shadow_symbol))
                         }
                         None => {
                             self.shadows
@@ -355,7 +357,11 @@ impl Scope {
                         region,
                     };
 
-                    Err((original_region, shadow, shadow_symbol))
+                    Err((
+                        Loc::at(original_region, original_symbol),
+                        shadow,
+                        shadow_symbol,
+                    ))
                 }
             }
             Ok(symbol) => Ok((symbol, None)),
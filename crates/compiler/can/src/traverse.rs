@@ -8,8 +8,8 @@ use crate::{
     abilities::AbilitiesStore,
     def::{Annotation, Def},
     expr::{
-        self, AnnotatedMark, ClosureData, Declarations, Expr, Field, OpaqueWrapFunctionData,
-        StructAccessorData,
+        self, AnnotatedMark, ClosureData, DeclarationTag, Declarations, Expr, Field,
+        OpaqueWrapFunctionData, StructAccessorData,
     },
     pattern::{DestructType, Pattern, RecordDestruct, TupleDestruct},
 };
@@ -164,7 +164,7 @@ pub fn walk_decls<V: Visitor>(visitor: &mut V, decls: &Declarations) {
     }
 }
 
-fn walk_decl<V: Visitor>(visitor: &mut V, decl: DeclarationInfo<'_>) {
+pub fn walk_decl<V: Visitor>(visitor: &mut V, decl: DeclarationInfo<'_>) {
     use DeclarationInfo::*;
 
     match decl {
@@ -729,6 +729,248 @@ pub fn find_closest_type_at(
     visitor.region_typ
 }
 
+/// Exhaustiveness errors for a `when` point at the region from its condition to the end of its
+/// last branch. Given that region, returns the region of the `when`'s first branch pattern.
+pub fn find_first_when_branch(when_region: Region, decls: &Declarations) -> Option<Region> {
+    let mut visitor = Finder {
+        when_region,
+        first_branch: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.first_branch;
+
+    struct Finder {
+        when_region: Region,
+        first_branch: Option<Region>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            self.first_branch.is_none() && region.contains(&self.when_region)
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if !self.should_visit(region) {
+                return;
+            }
+
+            if let Expr::When {
+                loc_cond, branches, ..
+            } = expr
+            {
+                if let (Some(first), Some(last)) = (branches.first(), branches.last()) {
+                    if Region::span_across(&loc_cond.region, &last.value.region) == self.when_region
+                    {
+                        self.first_branch = first.patterns.first().map(|p| p.pattern.region);
+                        return;
+                    }
+                }
+            }
+
+            walk_expr(self, expr, var);
+        }
+    }
+}
+
+/// Returns the regions of all declarations, defs, expressions, and patterns that contain
+/// [position], from the outermost to the innermost. Each region contains the next one.
+pub fn find_enclosing_regions_at(position: Position, decls: &Declarations) -> Vec<Region> {
+    let mut visitor = Collector {
+        position,
+        regions: Vec::new(),
+    };
+    visitor.visit_decls(decls);
+    return visitor.regions;
+
+    struct Collector {
+        position: Position,
+        regions: Vec<Region>,
+    }
+
+    impl Collector {
+        fn push(&mut self, region: Region) {
+            match self.regions.last() {
+                Some(last) if *last == region || !last.contains(&region) => {}
+                _ => self.regions.push(region),
+            }
+        }
+    }
+
+    impl Visitor for Collector {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region.contains_pos(self.position)
+        }
+
+        fn visit_decl(&mut self, decl: DeclarationInfo<'_>) {
+            let region = decl.region();
+            if self.should_visit(region) {
+                self.push(region);
+                walk_decl(self, decl);
+            }
+        }
+
+        fn visit_def(&mut self, def: &Def) {
+            let region = def.region();
+            if self.should_visit(region) {
+                self.push(region);
+                walk_def(self, def);
+            }
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if self.should_visit(region) {
+                self.push(region);
+                walk_expr(self, expr, var);
+            }
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            if self.should_visit(region) {
+                self.push(region);
+                walk_pattern(self, pattern);
+            }
+        }
+    }
+}
+
+/// Returns the symbols in scope at [position] together with their type variables: every top-level
+/// value, plus the arguments, local definitions, and `when` branch bindings that enclose
+/// [position]. Top-level values come first, then the enclosing bindings from the outside in.
+pub fn find_symbols_in_scope_at(
+    position: Position,
+    decls: &Declarations,
+) -> Vec<(Symbol, Variable)> {
+    let mut visitor = Collector {
+        position,
+        symbols: Vec::new(),
+    };
+
+    // top-level values are in scope everywhere, no matter where they are defined
+    for (index, tag) in decls.declarations.iter().enumerate() {
+        match tag {
+            DeclarationTag::Value
+            | DeclarationTag::Function(_)
+            | DeclarationTag::Recursive(_)
+            | DeclarationTag::TailRecursive(_) => {
+                visitor.bind(decls.symbols[index].value, decls.variables[index]);
+            }
+            DeclarationTag::Destructure(destruct_index) => {
+                let destruct = &decls.destructs[destruct_index.index()];
+
+                for (symbol, var) in destruct.pattern_vars.iter() {
+                    visitor.bind(*symbol, *var);
+                }
+            }
+            DeclarationTag::Expectation
+            | DeclarationTag::ExpectationFx
+            | DeclarationTag::MutualRecursion { .. } => {}
+        }
+    }
+
+    visitor.visit_decls(decls);
+    return visitor.symbols;
+
+    struct Collector {
+        position: Position,
+        symbols: Vec<(Symbol, Variable)>,
+    }
+
+    impl Collector {
+        fn bind(&mut self, symbol: Symbol, var: Variable) {
+            if !self.symbols.iter().any(|(bound, _)| *bound == symbol) {
+                self.symbols.push((symbol, var));
+            }
+        }
+    }
+
+    impl Visitor for Collector {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region.contains_pos(self.position)
+        }
+
+        fn visit_def(&mut self, def: &Def) {
+            // a def in an enclosing `let` binds its pattern in the body, even when the position is
+            // not inside the def itself
+            let opt_var = match def.loc_pattern.value {
+                Pattern::Identifier(..) => Some(def.expr_var),
+                _ => def.loc_pattern.value.opt_var(),
+            };
+            self.visit_pattern(&def.loc_pattern.value, def.loc_pattern.region, opt_var);
+
+            if self.should_visit(def.region()) {
+                walk_def(self, def);
+            }
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if !self.should_visit(region) {
+                return;
+            }
+
+            match expr {
+                Expr::When {
+                    cond_var,
+                    expr_var,
+                    loc_cond,
+                    branches,
+                    ..
+                } => {
+                    self.visit_expr(&loc_cond.value, loc_cond.region, *cond_var);
+
+                    // only the patterns of the branch we are in bind anything here
+                    for branch in branches.iter() {
+                        let in_guard = match &branch.guard {
+                            Some(guard) => self.should_visit(guard.region),
+                            None => false,
+                        };
+
+                        if in_guard || self.should_visit(branch.value.region) {
+                            // a bare identifier pattern has no variable of its own; it binds
+                            // the whole condition
+                            for pattern in branch.patterns.iter() {
+                                let loc_pattern = &pattern.pattern;
+                                self.visit_pattern(
+                                    &loc_pattern.value,
+                                    loc_pattern.region,
+                                    Some(*cond_var),
+                                );
+                            }
+
+                            walk_when_branch(self, branch, *expr_var);
+                        }
+                    }
+                }
+                _ => walk_expr(self, expr, var),
+            }
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, _region: Region, opt_var: Option<Variable>) {
+            match (pattern, opt_var) {
+                (Pattern::Identifier(symbol), Some(var)) | (Pattern::As(_, symbol), Some(var)) => {
+                    self.bind(*symbol, var)
+                }
+                _ => {}
+            }
+
+            walk_pattern(self, pattern);
+        }
+
+        fn visit_record_destruct(&mut self, destruct: &RecordDestruct, _region: Region) {
+            self.bind(destruct.symbol, destruct.var);
+
+            walk_record_destruct(self, destruct);
+        }
+
+        fn visit_tuple_destruct(&mut self, destruct: &TupleDestruct, _region: Region) {
+            self.visit_pattern(
+                &destruct.typ.1.value,
+                destruct.typ.1.region,
+                Some(destruct.typ.0),
+            )
+        }
+    }
+}
+
 /// Given an ability Foo has foo : ..., returns (T, foo1) if the symbol at the given region is a
 /// symbol foo1 that specializes foo for T. Otherwise if the symbol is foo but the specialization
 /// is unknown, (Foo, foo) is returned. Otherwise [None] is returned.
@@ -322,19 +322,10 @@ mod test_can {
         let arena = Bump::new();
         let CanExprOut { problems, .. } = can_expr_with(&arena, test_home(), src);
 
-        // Here we have 2 issues:
-        // 1. `g` doesn't match the previous annotation named `f`, so we
-        //     have a `SignatureDefMismatch`.
-        // 2. Thus, `g` is not defined then final reference to it is a
-        //    `LookupNotInScope`.
-        assert_eq!(problems.len(), 2);
-        assert!(problems.iter().all(|problem| {
-            matches!(
-                problem,
-                Problem::SignatureDefMismatch { .. }
-                    | Problem::RuntimeError(RuntimeError::LookupNotInScope { .. })
-            )
-        }));
+        // `g` doesn't match the previous annotation named `f`, so we have a
+        // `SignatureDefMismatch`. `g` is still defined, so the final reference to it is fine.
+        assert_eq!(problems.len(), 1);
+        assert!(matches!(problems[0], Problem::SignatureDefMismatch { .. }));
     }
 
     #[test]
@@ -350,19 +341,10 @@ mod test_can {
         let arena = Bump::new();
         let CanExprOut { problems, .. } = can_expr_with(&arena, test_home(), src);
 
-        // Here we have 2 issues:
-        // 1. `g` doesn't match the previous annotation named `f`, so we
-        //     have a `SignatureDefMismatch`.
-        // 2. Thus, `g` is not defined then final reference to it is a
-        //    `LookupNotInScope`.
-        assert_eq!(problems.len(), 2);
-        assert!(problems.iter().all(|problem| {
-            matches!(
-                problem,
-                Problem::SignatureDefMismatch { .. }
-                    | Problem::RuntimeError(RuntimeError::LookupNotInScope { .. })
-            )
-        }));
+        // `g` doesn't match the previous annotation named `f`, so we have a
+        // `SignatureDefMismatch`. `g` is still defined, so the final reference to it is fine.
+        assert_eq!(problems.len(), 1);
+        assert!(matches!(problems[0], Problem::SignatureDefMismatch { .. }));
     }
 
     #[test]
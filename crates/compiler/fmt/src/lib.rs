@@ -10,8 +10,12 @@ pub mod module;
 pub mod pattern;
 pub mod spaces;
 
+use annotation::Formattable;
 use bumpalo::{collections::String, Bump};
 use roc_parse::ast::Module;
+use roc_parse::parser::SyntaxError;
+use roc_region::all::{Loc, Region};
+use spaces::{fmt_default_newline, fmt_spaces};
 
 #[derive(Debug)]
 pub struct Ast<'a> {
@@ -19,6 +23,101 @@ pub struct Ast<'a> {
     pub defs: roc_parse::ast::Defs<'a>,
 }
 
+impl<'a> Ast<'a> {
+    /// Parse a whole module, failing on the first syntax error. Formatting needs every def,
+    /// because the ones that didn't parse would be left out of the output.
+    pub fn parse(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
+        use roc_parse::{
+            module::{module_defs, parse_header},
+            parser::Parser,
+            state::State,
+        };
+
+        let (module, state) = parse_header(arena, State::new(src.as_bytes()))
+            .map_err(|e| SyntaxError::Header(e.problem))?;
+
+        let (_, defs, _) = module_defs().parse(arena, state, 0).map_err(|(_, e)| e)?;
+
+        Ok(Ast { module, defs })
+    }
+
+    /// Parse as much of a module as possible. Defs that fail to parse are skipped and their
+    /// syntax errors returned; only a broken header fails outright.
+    pub fn parse_with_recovery(
+        arena: &'a Bump,
+        src: &'a str,
+    ) -> Result<(Ast<'a>, Vec<Loc<SyntaxError<'a>>>), SyntaxError<'a>> {
+        use roc_parse::{
+            module::{module_defs_with_recovery, parse_header},
+            state::State,
+        };
+
+        let (module, state) = parse_header(arena, State::new(src.as_bytes()))
+            .map_err(|e| SyntaxError::Header(e.problem))?;
+
+        let (defs, problems) = module_defs_with_recovery(arena, state);
+
+        Ok((Ast { module, defs }, problems))
+    }
+}
+
+/// Format a whole module: its header, then all of its defs.
+pub fn format_module(buf: &mut Buf, ast: &Ast) {
+    module::fmt_module(buf, &ast.module);
+
+    def::fmt_defs(buf, &ast.defs, 0);
+
+    buf.fmt_end_of_file();
+}
+
+/// Format only the top-level defs that overlap `range`, a region of the source `ast` was parsed
+/// from. Everything else in the file, including the comments before the first of those defs and
+/// after the last, is left alone.
+///
+/// Returns the region of the source to replace and its replacement, or `None` if `range` does not
+/// touch any def.
+pub fn format_range<'a>(arena: &'a Bump, ast: &Ast, range: Region) -> Option<(Region, &'a str)> {
+    let defs = &ast.defs;
+    let overlaps = |region: &Region| {
+        region.start().offset <= range.end().offset && range.start().offset <= region.end().offset
+    };
+
+    let first = defs.regions.iter().position(overlaps)?;
+    let last = defs.regions.iter().rposition(overlaps)?;
+
+    let mut buf = Buf::new_in(arena);
+    let mut prev_spaces = true;
+
+    for (index, def) in defs.defs().enumerate().take(last + 1).skip(first) {
+        if index > first {
+            let spaces_before = &defs.spaces[defs.space_before[index].indices()];
+
+            if prev_spaces {
+                fmt_spaces(&mut buf, spaces_before.iter(), 0);
+            } else {
+                fmt_default_newline(&mut buf, spaces_before, 0);
+            }
+        }
+
+        match def {
+            Ok(type_def) => type_def.format(&mut buf, 0),
+            Err(value_def) => value_def.format(&mut buf, 0),
+        }
+
+        if index < last {
+            let spaces_after = &defs.spaces[defs.space_after[index].indices()];
+
+            fmt_spaces(&mut buf, spaces_after.iter(), 0);
+
+            prev_spaces = !spaces_after.is_empty();
+        }
+    }
+
+    let replaced = Region::span_across(&defs.regions[first], &defs.regions[last]);
+
+    Some((replaced, buf.into_bump_str()))
+}
+
 #[derive(Debug)]
 pub struct Buf<'a> {
     text: String<'a>,
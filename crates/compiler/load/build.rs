@@ -107,6 +107,7 @@ fn write_types_for_module_real(module_id: ModuleId, filename: &str, output_path:
     let problems = report_problems(
         &module.sources,
         &module.interns,
+        |module_id| roc_load_internal::module::import_chain(&module.imported_by, module_id),
         &mut module.can_problems,
         &mut module.type_problems,
     );
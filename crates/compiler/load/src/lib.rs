@@ -19,11 +19,16 @@ const SKIP_SUBS_CACHE: bool = {
 pub use roc_load_internal::docs;
 pub use roc_load_internal::file::{
     ExecutionMode, ExpectMetadata, LoadConfig, LoadResult, LoadStart, LoadingProblem, Phase,
-    Threading,
+    Threading, WorkerArenas,
 };
+pub use roc_load_internal::layering;
 pub use roc_load_internal::module::{
-    CheckedModule, EntryPoint, Expectations, ExposedToHost, LoadedModule, MonomorphizedModule,
+    import_chain, CheckedModule, EntryPoint, Expectations, ExposedToHost, LoadedHeaders,
+    LoadedModule, ModuleGraph, ModuleNode, MonomorphizedModule,
 };
+pub use roc_load_internal::progress;
+pub use roc_load_internal::source_provider;
+pub use roc_load_internal::source_roots;
 pub use roc_solve::FunctionKind;
 
 #[allow(clippy::too_many_arguments)]
@@ -46,17 +51,13 @@ fn load<'a>(
     )
 }
 
-/// Load using only a single thread; used when compiling to webassembly
-#[allow(clippy::too_many_arguments)]
+/// Load using only a single thread; used when compiling to webassembly.
+/// The `threading` setting of `load_config` is ignored.
 pub fn load_single_threaded<'a>(
     arena: &'a Bump,
     load_start: LoadStart<'a>,
-    target_info: TargetInfo,
-    function_kind: FunctionKind,
-    render: RenderTarget,
-    palette: Palette,
     roc_cache_dir: RocCacheDir<'_>,
-    exec_mode: ExecutionMode,
+    load_config: LoadConfig,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let cached_subs = read_cached_types();
     let exposed_types = ExposedByModule::default();
@@ -65,13 +66,9 @@ pub fn load_single_threaded<'a>(
         arena,
         load_start,
         exposed_types,
-        target_info,
-        function_kind,
         cached_subs,
-        render,
-        palette,
-        exec_mode,
         roc_cache_dir,
+        load_config,
     )
 }
 
@@ -113,6 +110,7 @@ pub fn load_and_monomorphize_from_str<'a>(
     let exposed_types = ExposedByModule::default();
 
     match load(arena, load_start, exposed_types, roc_cache_dir, load_config)? {
+        HeadersLoaded(_) => unreachable!(),
         Monomorphized(module) => Ok(module),
         TypeChecked(module) => Err(LoadMonomorphizedError::ErrorModule(module)),
     }
@@ -132,11 +130,13 @@ pub fn load_and_monomorphize<'a>(
         load_config.render,
         roc_cache_dir,
         load_config.palette,
+        load_config.max_file_size,
     )?;
 
     let exposed_types = ExposedByModule::default();
 
     match load(arena, load_start, exposed_types, roc_cache_dir, load_config)? {
+        HeadersLoaded(_) => unreachable!(),
         Monomorphized(module) => Ok(module),
         TypeChecked(module) => Err(LoadMonomorphizedError::ErrorModule(module)),
     }
@@ -156,16 +156,56 @@ pub fn load_and_typecheck<'a>(
         load_config.render,
         roc_cache_dir,
         load_config.palette,
+        load_config.max_file_size,
     )?;
 
     let exposed_types = ExposedByModule::default();
 
     match load(arena, load_start, exposed_types, roc_cache_dir, load_config)? {
-        Monomorphized(_) => unreachable!(""),
+        HeadersLoaded(_) | Monomorphized(_) => unreachable!(""),
         TypeChecked(module) => Ok(module),
     }
 }
 
+/// Load the headers of this module and of everything it imports, and stop there. This is much
+/// cheaper than [load_and_typecheck], for tools that only need to know which modules exist,
+/// where they are, and what they import and expose.
+pub fn load_headers<'a>(
+    arena: &'a Bump,
+    filename: PathBuf,
+    roc_cache_dir: RocCacheDir<'_>,
+    load_config: LoadConfig,
+) -> Result<LoadedHeaders, LoadingProblem<'a>> {
+    use LoadResult::*;
+
+    let load_start = LoadStart::from_path(
+        arena,
+        filename,
+        load_config.render,
+        roc_cache_dir,
+        load_config.palette,
+        load_config.max_file_size,
+    )?;
+
+    let load_config = LoadConfig {
+        exec_mode: ExecutionMode::HeadersOnly,
+        ..load_config
+    };
+
+    // nothing gets type-checked, so there is no need to read the cached builtin types
+    match roc_load_internal::file::load(
+        arena,
+        load_start,
+        ExposedByModule::default(),
+        MutMap::default(),
+        roc_cache_dir,
+        load_config,
+    )? {
+        HeadersLoaded(headers) => Ok(headers),
+        TypeChecked(_) | Monomorphized(_) => unreachable!(""),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn load_and_typecheck_str<'a>(
     arena: &'a Bump,
@@ -185,17 +225,15 @@ pub fn load_and_typecheck_str<'a>(
     // NOTE: this function is meant for tests, and so we use single-threaded
     // solving so we don't use too many threads per-test. That gives higher
     // throughput for the test run overall
-    match load_single_threaded(
-        arena,
-        load_start,
-        target_info,
-        function_kind,
+    let load_config = LoadConfig {
         render,
         palette,
-        roc_cache_dir,
-        ExecutionMode::Check,
-    )? {
-        Monomorphized(_) => unreachable!(""),
+        threading: Threading::Single,
+        ..LoadConfig::new(target_info, function_kind, ExecutionMode::Check)
+    };
+
+    match load_single_threaded(arena, load_start, roc_cache_dir, load_config)? {
+        HeadersLoaded(_) | Monomorphized(_) => unreachable!(""),
         TypeChecked(module) => Ok(module),
     }
 }
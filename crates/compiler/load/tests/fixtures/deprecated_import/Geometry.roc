@@ -0,0 +1,8 @@
+interface Geometry exposes [origin, zero] imports []
+
+## Deprecated: use `zero` instead
+origin : (F64, F64)
+origin = zero
+
+zero : (F64, F64)
+zero = (0, 0)
@@ -0,0 +1,3 @@
+interface Main exposes [main] imports [Geometry]
+
+main = Geometry.origin
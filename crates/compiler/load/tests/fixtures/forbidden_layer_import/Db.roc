@@ -0,0 +1,3 @@
+interface Db exposes [query] imports []
+
+query = 1
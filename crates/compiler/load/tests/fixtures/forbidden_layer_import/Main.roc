@@ -0,0 +1,3 @@
+interface Main exposes [main] imports [Db]
+
+main = Db.query
@@ -0,0 +1,3 @@
+interface Http exposes [get] imports [Parser]
+
+get = 1
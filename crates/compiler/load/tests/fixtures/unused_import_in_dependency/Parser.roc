@@ -0,0 +1,3 @@
+interface Parser exposes [null] imports []
+
+null = 0
@@ -177,7 +177,8 @@ pub fn can_expr_with<'a>(
     let mut scope = Scope::new(home, IdentIds::default(), Default::default());
 
     let dep_idents = IdentIds::exposed_builtins(0);
-    let mut env = Env::new(arena, home, &dep_idents, &module_ids);
+    let exposed_values = Default::default();
+    let mut env = Env::new(arena, home, &dep_idents, &exposed_values, &module_ids);
     let (loc_expr, output) = canonicalize_expr(
         &mut env,
         &mut var_store,
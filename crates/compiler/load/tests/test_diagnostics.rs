@@ -0,0 +1,150 @@
+//! Snapshot tests for the problems the loader reports on small multi-module projects.
+//!
+//! Each fixture is a directory under `tests/fixtures/` with a `Main.roc` root module, any modules
+//! it imports, and optionally a `roc-layers.txt`. The test loads the fixture, renders every
+//! module's problems the way `roc check` would, and compares the result against an inline
+//! snapshot. Run `cargo insta review` to accept changes after rewording a report or changing the
+//! order modules are loaded in.
+extern crate bumpalo;
+extern crate roc_reporting;
+
+#[cfg(test)]
+mod test_diagnostics {
+    use bumpalo::Bump;
+    use roc_load::layering::LayerRules;
+    use roc_load::source_provider::RealFileSystem;
+    use roc_load::{ExecutionMode, LoadConfig, LoadingProblem, Threading};
+    use roc_packaging::cache::RocCacheDir;
+    use roc_reporting::report::{
+        can_problem, type_problem, CiWrite, RenderTarget, RocDocAllocator,
+    };
+    use roc_solve::FunctionKind;
+    use std::path::{Path, PathBuf};
+
+    const FIXTURES_DIR: &str = "tests/fixtures";
+
+    /// Load the fixture and render all of its problems, ordered by module path.
+    fn fixture_reports(name: &str) -> String {
+        use ven_pretty::DocAllocator;
+
+        let arena = Bump::new();
+        let dir = Path::new(FIXTURES_DIR).join(name);
+
+        let load_config = LoadConfig {
+            render: RenderTarget::Generic,
+            threading: Threading::Single,
+            layer_rules: LayerRules::from_dir(&RealFileSystem, &dir).unwrap(),
+            ..LoadConfig::new(
+                roc_target::TargetInfo::default_x86_64(),
+                FunctionKind::LambdaSet,
+                ExecutionMode::Check,
+            )
+        };
+
+        let mut loaded = match roc_load::load_and_typecheck(
+            &arena,
+            dir.join("Main.roc"),
+            RocCacheDir::Disallowed,
+            load_config,
+        ) {
+            Ok(loaded) => loaded,
+            Err(LoadingProblem::FormattedReport(report)) => return report,
+            Err(problem) => panic!("{name} failed to load: {problem:?}"),
+        };
+
+        let mut modules: Vec<(PathBuf, _)> = loaded
+            .sources
+            .iter()
+            .filter(|(_, (path, _))| path.starts_with(&dir))
+            .map(|(module_id, (path, _))| (path.clone(), *module_id))
+            .collect();
+        modules.sort();
+
+        let mut buf = String::new();
+
+        for (path, module_id) in modules {
+            let can_problems = loaded.can_problems.remove(&module_id).unwrap_or_default();
+            let type_problems = loaded.type_problems.remove(&module_id).unwrap_or_default();
+
+            if can_problems.is_empty() && type_problems.is_empty() {
+                continue;
+            }
+
+            let src = &loaded.sources[&module_id].1;
+            let src_lines: Vec<&str> = src.split('\n').collect();
+            let lines = &loaded.line_infos[&module_id];
+            let alloc = RocDocAllocator::new(&src_lines, module_id, &loaded.interns);
+
+            let can_reports = can_problems
+                .into_iter()
+                .map(|problem| can_problem(&alloc, lines, path.clone(), problem));
+            let type_reports = type_problems
+                .into_iter()
+                .flat_map(|problem| type_problem(&alloc, lines, path.clone(), problem));
+            let reports = can_reports
+                .chain(type_reports)
+                .map(|report| report.pretty(&alloc));
+
+            if !buf.is_empty() {
+                buf.push('\n');
+            }
+
+            alloc
+                .stack(reports)
+                .append(alloc.line())
+                .1
+                .render_raw(70, &mut CiWrite::new(&mut buf))
+                .unwrap();
+        }
+
+        buf
+    }
+
+    #[test]
+    fn unused_import_in_dependency() {
+        insta::assert_snapshot!(fixture_reports("unused_import_in_dependency"), @r###"
+        ── UNUSED IMPORT ───────── tests/fixtures/unused_import_in_dependency/Http.roc ─
+
+        Nothing from Parser is used in this module.
+
+        1│  interface Http exposes [get] imports [Parser]
+                                                  ^^^^^^
+
+        Since Parser isn't used, you don't need to import it.
+        "###);
+    }
+
+    #[test]
+    fn forbidden_layer_import() {
+        insta::assert_snapshot!(fixture_reports("forbidden_layer_import"), @r###"
+        ── FORBIDDEN IMPORT ─────────── tests/fixtures/forbidden_layer_import/Main.roc ─
+
+        This module is not allowed to import Db:
+
+        1│  interface Main exposes [main] imports [Db]
+                                                   ^^
+
+        Your project's layering rules forbid this import:
+
+            Main may not import Db.*
+
+        Can you get what you need from an allowed module instead?
+        "###);
+    }
+
+    #[test]
+    fn deprecated_import() {
+        insta::assert_snapshot!(fixture_reports("deprecated_import"), @r###"
+        ── DEPRECATED ────────────────────── tests/fixtures/deprecated_import/Main.roc ─
+
+        This uses `Geometry.origin`, which is deprecated:
+
+        3│  main = Geometry.origin
+                   ^^^^^^^^^^^^^^^
+
+        Its documentation says:
+
+            use `zero` instead
+        "###);
+    }
+}
@@ -123,12 +123,13 @@ mod test_reporting {
             let mut file = File::create(file_path).unwrap();
             writeln!(file, "{module_src}").unwrap();
             let load_config = LoadConfig {
-                target_info: roc_target::TargetInfo::default_x86_64(),
                 render: RenderTarget::Generic,
-                palette: DEFAULT_PALETTE,
                 threading: Threading::Single,
-                exec_mode: ExecutionMode::Check,
-                function_kind: FunctionKind::LambdaSet,
+                ..LoadConfig::new(
+                    roc_target::TargetInfo::default_x86_64(),
+                    FunctionKind::LambdaSet,
+                    ExecutionMode::Check,
+                )
             };
             let result = roc_load::load_and_typecheck(
                 arena,
@@ -496,6 +497,30 @@ mod test_reporting {
     "###
     );
 
+    test_report!(
+        non_ascii_before_error,
+        indoc!(
+            r#"
+            { greeting: "héllo 👋", empty: List.isempty }
+        "#
+        ),
+        @r###"
+    ── NOT EXPOSED ─────────────────────────────────────────── /code/proj/Main.roc ─
+
+    The List module does not expose `isempty`:
+
+    4│      { greeting: "héllo 👋", empty: List.isempty }
+                                           ^^^^^^^^^^^^
+
+    Did you mean one of these?
+
+        List.isEmpty
+        List.set
+        List.get
+        List.keepIf
+    "###
+    );
+
     test_report!(
         report_unused_def,
         indoc!(
@@ -658,13 +683,42 @@ mod test_reporting {
 
     Did you mean one of these?
 
+        Bool.true
         Frac
         Num
         Str
-        Err
+
+    Tip: Bool exposes a value with this name. You can refer to it as
+    `Bool.true`, or expose it in your imports.
     "###
     );
 
+    test_report!(
+        typo_suggests_qualified_exposed_value,
+        indoc!(
+            r#"
+            List.len (sortAs [3, 1, 2])
+            "#
+        ),
+        |golden| {
+            assert!(golden.contains("UNRECOGNIZED NAME"), "{golden}");
+            assert!(golden.contains("List.sortAsc"), "{golden}");
+        }
+    );
+
+    test_report!(
+        unexposed_values_are_not_suggested,
+        indoc!(
+            r#"
+            getUnsafe [1, 2] 0
+            "#
+        ),
+        |golden| {
+            assert!(golden.contains("UNRECOGNIZED NAME"), "{golden}");
+            assert!(!golden.contains("List.getUnsafe"), "{golden}");
+        }
+    );
+
     test_report!(
         report_precedence_problem_multiline,
         indoc!(
@@ -3165,20 +3219,43 @@ mod test_reporting {
             bar : Num.I64
             foo = \x -> x
 
-            # NOTE: neither bar or foo are defined at this point
-            4
+            # NOTE: foo is still defined, just without the annotation
+            foo 4
             "#
         ),
         @r###"
     ── NAMING PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
 
-    This annotation does not match the definition immediately following
-    it:
+    The annotation for `bar` is not followed by its definition:
 
     4│>      bar : Num.I64
     5│>      foo = \x -> x
 
-    Is it a typo? If not, put either a newline or comment between them.
+    The definition right after it is for `foo`. If they don't belong
+    together, put either a newline or comment between them.
+    "###
+    );
+
+    test_report!(
+        annotation_definition_typo,
+        indoc!(
+            r#"
+            total : Num.I64
+            totla = 1
+
+            totla
+            "#
+        ),
+        @r###"
+    ── NAMING PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
+
+    The annotation for `total` is not followed by its definition:
+
+    4│>      total : Num.I64
+    5│>      totla = 1
+
+    The definition right after it is for `totla`, which is spelled almost
+    the same. Is that a typo?
     "###
     );
 
@@ -11352,6 +11429,74 @@ In roc, functions are always written as a lambda, like{}
     "###
     );
 
+    test_report!(
+        shadowed_builtin_import,
+        indoc!(
+            r#"
+            app "test" imports [List.{ concat }] provides [main] to "./platform"
+
+            main = \concat -> concat
+            "#
+        ),
+    @r###"
+    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+
+    The `concat` name is already used by the builtin `List.concat`, which
+    this module imports here:
+
+    1│  app "test" imports [List.{ concat }] provides [main] to "./platform"
+                                   ^^^^^^
+
+    But then it's defined a second time here:
+
+    3│  main = \concat -> concat
+                ^^^^^^
+
+    Give this one a different name, or remove `concat` from the import and
+    write `List.concat` where you mean the builtin.
+    "###
+    );
+
+    test_report!(
+        top_level_def_shadows_import,
+        indoc!(
+            r#"
+            app "test" imports [List.{ concat }] provides [main] to "./platform"
+
+            concat = \a, _ -> a
+
+            main = concat [1] [2]
+            "#
+        ),
+    @r###"
+    ── DUPLICATE NAME ──────────────────────────────────────── /code/proj/Main.roc ─
+
+    The `concat` name is already used by the builtin `List.concat`, which
+    this module imports here:
+
+    1│  app "test" imports [List.{ concat }] provides [main] to "./platform"
+                                   ^^^^^^
+
+    But then it's defined a second time here:
+
+    3│  concat = \a, _ -> a
+        ^^^^^^
+
+    Give this one a different name, or remove `concat` from the import and
+    write `List.concat` where you mean the builtin.
+
+    ── UNUSED DEFINITION ───────────────────────────────────── /code/proj/Main.roc ─
+
+    `concat` is not used anywhere in your code.
+
+    3│  concat = \a, _ -> a
+        ^^^^^^
+
+    If you didn't intend on using `concat` then remove it so future
+    readers of your code don't wonder why it is there.
+    "###
+    );
+
     test_report!(
         #[ignore = "https://github.com/roc-lang/roc/issues/4096"]
         unnecessary_builtin_module_import,
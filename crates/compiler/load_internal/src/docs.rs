@@ -1,12 +1,14 @@
 use crate::docs::DocEntry::DetachedDoc;
 use crate::docs::TypeAnnotation::{Apply, BoundVariable, Function, NoTypeAnn, Record, TagUnion};
 use roc_can::scope::Scope;
+use roc_collections::soa::EitherIndex;
 use roc_collections::VecSet;
 use roc_module::ident::ModuleName;
 use roc_module::symbol::{IdentIds, ModuleId, ModuleIds, Symbol};
 use roc_parse::ast::AssignedField;
 use roc_parse::ast::{self, ExtractSpaces, TypeHeader};
 use roc_parse::ast::{CommentOrNewline, TypeDef, ValueDef};
+use roc_region::all::Loc;
 
 // Documentation generation requirements
 
@@ -180,23 +182,9 @@ fn generate_entry_docs(
         acc.push(DetachedDoc(docs));
     }
 
-    let mut before_comments_or_new_lines: Option<&[CommentOrNewline]> = None;
-    let mut scratchpad = Vec::new();
-
-    for (index, either_index) in defs.tags.iter().enumerate() {
-        let spaces_before = &defs.spaces[defs.space_before[index].indices()];
-
-        scratchpad.clear();
-        scratchpad.extend(
-            before_comments_or_new_lines
-                .take()
-                .iter()
-                .flat_map(|e| e.iter()),
-        );
-        scratchpad.extend(spaces_before);
-
-        let docs = comments_or_new_lines_to_docs(&scratchpad);
+    let docs_above_defs = spaces_above_defs(defs).map(comments_or_new_lines_to_docs);
 
+    for (either_index, docs) in defs.tags.iter().zip(docs_above_defs) {
         match either_index.split() {
             Err(value_index) => match &defs.value_defs[value_index.index()] {
                 ValueDef::Annotation(loc_pattern, loc_ann) => {
@@ -351,15 +339,127 @@ fn generate_entry_docs(
                 }
             },
         }
+    }
+
+    if let Some(last) = defs.space_after.last() {
+        let it = defs.spaces[last.indices()].iter();
+
+        for detached_doc in detached_docs_from_comments_and_new_lines(it) {
+            acc.push(DetachedDoc(detached_doc));
+        }
+    }
+
+    acc
+}
+
+/// The comments and newlines above each def in `defs`, in order. Those between two defs are
+/// split between the spaces after the first and the spaces before the second, so this chains
+/// them back together.
+fn spaces_above_defs<'a>(
+    defs: &'a roc_parse::ast::Defs<'a>,
+) -> impl Iterator<Item = impl Iterator<Item = &'a CommentOrNewline<'a>>> {
+    (0..defs.tags.len()).map(move |index| {
+        let after_previous = match index.checked_sub(1) {
+            Some(previous) => &defs.spaces[defs.space_after[previous].indices()],
+            None => &[],
+        };
+
+        after_previous
+            .iter()
+            .chain(&defs.spaces[defs.space_before[index].indices()])
+    })
+}
+
+/// The name a top-level def introduces, if it is a plain name
+fn def_name<'a>(
+    defs: &roc_parse::ast::Defs<'a>,
+    either_index: EitherIndex<TypeDef<'a>, ValueDef<'a>>,
+) -> Option<&'a str> {
+    use roc_parse::ast::Pattern;
+
+    match either_index.split() {
+        Err(value_index) => match &defs.value_defs[value_index.index()] {
+            ValueDef::Annotation(Loc { value: pattern, .. }, _)
+            | ValueDef::Body(Loc { value: pattern, .. }, _)
+            | ValueDef::AnnotatedBody {
+                ann_pattern: Loc { value: pattern, .. },
+                ..
+            } => match pattern {
+                Pattern::Identifier(identifier) => Some(*identifier),
+                _ => None,
+            },
+            ValueDef::Dbg { .. } | ValueDef::Expect { .. } | ValueDef::ExpectFx { .. } => None,
+        },
+        Ok(type_index) => match &defs.type_defs[type_index.index()] {
+            TypeDef::Alias { header, .. }
+            | TypeDef::Opaque { header, .. }
+            | TypeDef::Ability { header, .. } => Some(header.name.value),
+        },
+    }
+}
+
+/// A doc comment line starting with this marks the def below it as deprecated, and modules that
+/// use the def get a warning with the rest of the line, e.g.
+///
+/// ```text
+/// ## Deprecated: use `walk` instead
+/// fold : List a, s, (s, a -> s) -> s
+/// ```
+pub const DEPRECATED_PREFIX: &str = "Deprecated:";
+
+/// If these docs mark their def as deprecated, the message that goes with it (possibly empty).
+pub fn deprecation_message(docs: &str) -> Option<&str> {
+    docs.lines()
+        .find_map(|line| line.trim().strip_prefix(DEPRECATED_PREFIX))
+        .map(str::trim)
+}
+
+/// The deprecation message of each top-level def whose doc comment has one, along with the name
+/// the def introduces. Only the doc comment lines themselves are checked, so no docs are built
+/// up for the many defs that aren't deprecated.
+pub(crate) fn deprecations_by_name<'a>(
+    defs: &'a roc_parse::ast::Defs<'a>,
+) -> Vec<(&'a str, &'a str)> {
+    let mut acc = Vec::new();
+
+    for (either_index, spaces) in defs.tags.iter().zip(spaces_above_defs(defs)) {
+        let mut message = None;
+
+        // like comments_or_new_lines_to_docs, only the doc comment right above the def counts
+        for comment_or_new_line in spaces {
+            match comment_or_new_line {
+                CommentOrNewline::DocComment(line) => {
+                    message = message.or_else(|| deprecation_message(line));
+                }
+                CommentOrNewline::Newline | CommentOrNewline::LineComment(_) => {
+                    message = None;
+                }
+            }
+        }
 
-        let spaces_after = &defs.spaces[defs.space_after[index].indices()];
-        before_comments_or_new_lines = Some(spaces_after);
+        if let (Some(name), Some(message)) = (def_name(defs, *either_index), message) {
+            acc.push((name, message));
+        }
     }
 
-    let it = before_comments_or_new_lines.iter().flat_map(|e| e.iter());
+    acc
+}
 
-    for detached_doc in detached_docs_from_comments_and_new_lines(it) {
-        acc.push(DetachedDoc(detached_doc));
+/// The `##` doc comment above each top-level def that has one, along with the name the def
+/// introduces. Unlike [generate_module_docs], this covers every def, exposed or not, and
+/// doesn't render anything.
+pub(crate) fn doc_comments_by_name<'a>(
+    defs: &'a roc_parse::ast::Defs<'a>,
+) -> Vec<(&'a str, String)> {
+    let mut acc = Vec::new();
+
+    for (either_index, spaces) in defs.tags.iter().zip(spaces_above_defs(defs)) {
+        if let (Some(name), Some(docs)) = (
+            def_name(defs, *either_index),
+            comments_or_new_lines_to_docs(spaces),
+        ) {
+            acc.push((name, docs));
+        }
     }
 
     acc
@@ -682,12 +782,12 @@ fn tag_to_doc(in_func_ann: bool, tag: ast::Tag) -> Option<Tag> {
     }
 }
 
-fn comments_or_new_lines_to_docs<'a>(
-    comments_or_new_lines: &'a [roc_parse::ast::CommentOrNewline<'a>],
+fn comments_or_new_lines_to_docs<'b, 'a: 'b>(
+    comments_or_new_lines: impl IntoIterator<Item = &'b roc_parse::ast::CommentOrNewline<'a>>,
 ) -> Option<String> {
     let mut docs = String::new();
 
-    for comment_or_new_line in comments_or_new_lines.iter() {
+    for comment_or_new_line in comments_or_new_lines {
         match comment_or_new_line {
             CommentOrNewline::DocComment(doc_str) => {
                 docs.push_str(doc_str);
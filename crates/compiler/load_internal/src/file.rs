@@ -1,20 +1,26 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::docs::ModuleDocumentation;
+use crate::layering::LayerRules;
 use crate::module::{
     CheckedModule, ConstrainedModule, EntryPoint, Expectations, ExposedToHost,
-    FoundSpecializationsModule, LateSpecializationsModule, LoadedModule, ModuleHeader,
-    ModuleTiming, MonomorphizedModule, ParsedModule, ToplevelExpects, TypeCheckedModule,
+    FoundSpecializationsModule, LateSpecializationsModule, LoadedHeaders, LoadedModule,
+    ModuleHeader, ModuleNode, ModuleTiming, MonomorphizedModule, ParsedModule, ToplevelExpects,
+    TypeCheckedModule,
 };
 use crate::module_cache::ModuleCache;
+use crate::progress::{ModuleProblems, ProgressListener};
+use crate::source_provider::{RealFileSystem, SourceProvider};
+use crate::source_roots::{near_misses, SourcePackage, SourceRoots};
 use bumpalo::{collections::CollectIn, Bump};
-use crossbeam::channel::{bounded, Sender};
+use crossbeam::channel::{unbounded, Sender};
 use crossbeam::deque::{Injector, Stealer, Worker};
 use crossbeam::thread;
 use parking_lot::Mutex;
 use roc_builtins::roc::module_source;
 use roc_can::abilities::{AbilitiesStore, PendingAbilitiesStore, ResolvedImpl};
 use roc_can::constraint::{Constraint as ConstraintSoa, Constraints, TypeOrVar};
+use roc_can::env::{ExposedValues, ModuleExposes};
 use roc_can::expr::{DbgLookup, Declarations, ExpectLookup, PendingDerives};
 use roc_can::module::{
     canonicalize_module_defs, ExposedByModule, ExposedForModule, ExposedModuleTypes, Module,
@@ -51,20 +57,25 @@ use roc_parse::ast::{
     self, CommentOrNewline, Expr, ExtractSpaces, Pattern, Spaced, StrLiteral, ValueDef,
 };
 use roc_parse::header::{
-    ExposedName, HeaderType, ImportsEntry, PackageEntry, PackageHeader, PlatformHeader, To,
-    TypedIdent,
+    ExposedName, HeaderRole, HeaderType, ImportsEntry, PackageEntry, PackageHeader, PlatformHeader,
+    To, TypedIdent,
 };
-use roc_parse::module::module_defs;
+use roc_parse::module::{module_defs, module_defs_with_recovery};
 use roc_parse::parser::{FileError, Parser, SourceError, SyntaxError};
 use roc_problem::Severity;
 use roc_region::all::{LineInfo, Loc, Region};
 #[cfg(not(target_family = "wasm"))]
 use roc_reporting::report::to_https_problem_report_string;
-use roc_reporting::report::{to_file_problem_report_string, Palette, RenderTarget};
+use roc_reporting::report::{
+    recovered_syntax_problem, to_ambiguous_module_report, to_file_problem_report_string,
+    to_file_report_string, to_file_too_large_report, to_filename_case_mismatch_report,
+    to_imported_app_module_report, to_invalid_utf8_report, to_module_not_found_report, Palette,
+    RenderTarget, DEFAULT_PALETTE,
+};
 use roc_solve::module::{extract_module_owned_implementations, SolveConfig, Solved, SolvedModule};
 use roc_solve::FunctionKind;
 use roc_solve_problem::TypeError;
-use roc_target::TargetInfo;
+use roc_target::{Architecture, TargetInfo};
 use roc_types::subs::{CopiedImport, ExposedTypesStorageSubs, Subs, VarStore, Variable};
 use roc_types::types::{Alias, Types};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
@@ -98,6 +109,13 @@ const MODULE_SEPARATOR: char = '.';
 
 const EXPANDED_STACK_SIZE: usize = 8 * 1024 * 1024;
 
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// By default, source files larger than this are rejected before they are read into the arena.
+/// Regions store byte offsets as u32, and long before that limit a (usually generated) file
+/// would exhaust memory while it gets parsed.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 256 * 1024 * 1024;
+
 macro_rules! log {
     ($($arg:tt)*) => (dbg_do!(ROC_PRINT_LOAD_LOG, println!($($arg)*)))
 }
@@ -110,11 +128,51 @@ pub struct LoadConfig {
     pub threading: Threading,
     pub exec_mode: ExecutionMode,
     pub function_kind: FunctionKind,
+    /// Forbidden edges in the module import graph; see [crate::layering]
+    pub layer_rules: LayerRules,
+    /// Told about each module as it is loaded; see [crate::progress]
+    pub progress: Option<Arc<dyn ProgressListener>>,
+    /// Modules whose files are larger than this many bytes are reported instead of read
+    pub max_file_size: u64,
+    /// Keep going past a def that fails to parse, and report it as a problem of its module,
+    /// instead of stopping the whole load. Editors want this, to check half-typed code.
+    pub recover_from_syntax_errors: bool,
+}
+
+impl LoadConfig {
+    /// Reports are rendered for a color terminal, all available threads are used, there are no
+    /// layering rules or progress listener, files are limited to [DEFAULT_MAX_FILE_SIZE], and
+    /// the first syntax error stops the load. Override fields with struct update syntax.
+    pub fn new(
+        target_info: TargetInfo,
+        function_kind: FunctionKind,
+        exec_mode: ExecutionMode,
+    ) -> Self {
+        Self {
+            target_info,
+            render: RenderTarget::ColorTerminal,
+            palette: DEFAULT_PALETTE,
+            threading: Threading::AllAvailable,
+            exec_mode,
+            function_kind,
+            layer_rules: LayerRules::default(),
+            progress: None,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            recover_from_syntax_errors: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum ExecutionMode {
+    /// Stops once the header of every module in the dependency tree has been loaded.
+    /// Nothing is parsed past the headers, canonicalized or type-checked.
+    HeadersOnly,
     Check,
+    /// Like [`ExecutionMode::Check`], but only the problems found are kept. Every module's
+    /// declarations and solved types are dropped as soon as it is solved, which saves a lot of
+    /// memory on big projects.
+    CheckProblemsOnly,
     Executable,
     /// Like [`ExecutionMode::Executable`], but stops in the presence of type errors.
     ExecutableIfCheck,
@@ -128,11 +186,16 @@ impl ExecutionMode {
         use ExecutionMode::*;
 
         match self {
+            HeadersOnly => Phase::LoadHeader,
             Executable => Phase::MakeSpecializations,
-            Check | ExecutableIfCheck | Test => Phase::SolveTypes,
+            Check | CheckProblemsOnly | ExecutableIfCheck | Test => Phase::SolveTypes,
         }
     }
 
+    fn keeps_declarations(&self) -> bool {
+        !matches!(self, Self::CheckProblemsOnly)
+    }
+
     fn build_if_checks(&self) -> bool {
         matches!(self, Self::ExecutableIfCheck | Self::Test)
     }
@@ -184,6 +247,7 @@ fn start_phase<'a>(
                             module_ids: Arc::clone(&state.arc_modules),
                             shorthands: Arc::clone(&state.arc_shorthands),
                             ident_ids_by_module: Arc::clone(&state.ident_ids_by_module),
+                            max_file_size: state.max_file_size,
                         }
                     }
                 }
@@ -192,7 +256,10 @@ fn start_phase<'a>(
                 // parse the file
                 let header = state.module_cache.headers.remove(&module_id).unwrap();
 
-                BuildTask::Parse { header }
+                BuildTask::Parse {
+                    header,
+                    recover_from_syntax_errors: state.recover_from_syntax_errors,
+                }
             }
             Phase::CanonicalizeAndConstrain => {
                 // canonicalize the file
@@ -297,6 +364,9 @@ fn start_phase<'a>(
                 BuildTask::CanonicalizeAndConstrain {
                     parsed,
                     dep_idents,
+                    // Only the headers of modules this one imports are sure to be loaded by now,
+                    // so anything else would make suggestions depend on the load order
+                    exposed_values: transitively_imported_exposes(state, module_id),
                     exposed_symbols,
                     module_ids,
                     aliases,
@@ -575,6 +645,8 @@ enum Msg<'a> {
         #[cfg(debug_assertions)]
         checkmate: Option<roc_checkmate::Collector>,
     },
+    /// The task is to only load headers; every module's header is now loaded, we are done
+    FinishedAllHeaders,
     FinishedAllTypeChecking {
         solved_subs: Solved<Subs>,
         exposed_vars_by_symbol: Vec<(Symbol, Variable)>,
@@ -637,6 +709,9 @@ enum Msg<'a> {
 struct CanAndCon {
     constrained_module: ConstrainedModule,
     canonicalization_problems: Vec<roc_problem::can::Problem>,
+    occurrences: Vec<Loc<Symbol>>,
+    bindings: Vec<Loc<Symbol>>,
+    doc_comments: MutMap<Symbol, String>,
     module_docs: Option<ModuleDocumentation>,
 }
 
@@ -719,6 +794,10 @@ struct State<'a> {
 
     pub exposed_symbols_by_module: MutMap<ModuleId, VecSet<Symbol>>,
 
+    /// The values in `exposed_symbols_by_module`, by name. Canonicalization suggests them
+    /// for names that aren't in scope.
+    pub exposed_values: ExposedValues,
+
     pub timings: MutMap<ModuleId, ModuleTiming>,
 
     // Each thread gets its own layout cache. When one "pending specializations"
@@ -731,6 +810,14 @@ struct State<'a> {
     pub render: RenderTarget,
     pub palette: Palette,
     pub exec_mode: ExecutionMode,
+    pub layer_rules: LayerRules,
+    pub progress: Option<Arc<dyn ProgressListener>>,
+    pub max_file_size: u64,
+    pub recover_from_syntax_errors: bool,
+
+    /// The packages named in the project's roc-roots.txt, registered along with the root
+    /// module's own packages
+    pub source_packages: Vec<SourcePackage>,
 
     /// All abilities across all modules.
     pub world_abilities: WorldAbilities,
@@ -763,6 +850,11 @@ impl<'a> State<'a> {
         palette: Palette,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
+        layer_rules: LayerRules,
+        progress: Option<Arc<dyn ProgressListener>>,
+        max_file_size: u64,
+        recover_from_syntax_errors: bool,
+        source_packages: Vec<SourcePackage>,
     ) -> Self {
         let arc_shorthands = Arc::new(Mutex::new(MutMap::default()));
         let cache_dir = roc_packaging::cache::roc_cache_dir();
@@ -792,12 +884,18 @@ impl<'a> State<'a> {
             ident_ids_by_module,
             declarations_by_id: MutMap::default(),
             exposed_symbols_by_module: MutMap::default(),
+            exposed_values: Default::default(),
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
             cached_types: Arc::new(Mutex::new(cached_types)),
             render,
             palette,
             exec_mode,
+            layer_rules,
+            progress,
+            max_file_size,
+            recover_from_syntax_errors,
+            source_packages,
             make_specializations_pass: MakeSpecializationsPass::Pass(1),
             world_abilities: Default::default(),
             layout_interner: GlobalLayoutInterner::with_capacity(128, target_info),
@@ -859,14 +957,17 @@ enum BuildTask<'a> {
         module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
         shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
+        max_file_size: u64,
     },
     Parse {
         header: ModuleHeader<'a>,
+        recover_from_syntax_errors: bool,
     },
     CanonicalizeAndConstrain {
         parsed: ParsedModule<'a>,
         module_ids: ModuleIds,
         dep_idents: IdentIdsByModule,
+        exposed_values: ExposedValues,
         exposed_symbols: VecSet<Symbol>,
         aliases: MutMap<Symbol, Alias>,
         abilities_store: PendingAbilitiesStore,
@@ -945,17 +1046,71 @@ pub enum LoadingProblem<'a> {
     UnexpectedHeader(String),
 
     ErrJoiningWorkerThreads,
-    TriedToImportAppModule,
+    /// A module imports a file with an `app` header. An app can only be the root module;
+    /// platforms get at its values through their `requires` instead.
+    TriedToImportAppModule {
+        filename: PathBuf,
+        module_name: PQModuleName<'a>,
+        /// The files of the modules importing it, with their kinds of header.
+        /// Only known once the coordinator has the error.
+        imported_by: Vec<(HeaderRole, PathBuf)>,
+    },
 
     /// a formatted report
     FormattedReport(String),
 
     ImportCycle(PathBuf, Vec<ModuleId>),
     IncorrectModuleName(FileError<'a, IncorrectModuleName<'a>>),
+    FileTooLarge {
+        filename: PathBuf,
+        size: u64,
+        limit: u64,
+    },
+    /// The file is not valid UTF-8, e.g. because it was saved as Latin-1 or UTF-16.
+    /// `line` and `column` are 1-based, and the column counts characters.
+    InvalidUtf8 {
+        filename: PathBuf,
+        byte_offset: usize,
+        line: u32,
+        column: u32,
+    },
+    /// More than one source root has a file for this module
+    AmbiguousModule {
+        module_name: String,
+        candidates: Vec<PathBuf>,
+    },
+    /// The module's file is only there with different casing, e.g. `foo/bar.roc` for `Foo.Bar`
+    FilenameCaseMismatch {
+        expected: PathBuf,
+        found: PathBuf,
+    },
+    /// An imported module's file is not in any of the places we looked
+    ModuleNotFound {
+        module_name: String,
+        searched_paths: Vec<PathBuf>,
+        /// Existing files the import might have meant, closest first
+        suggestions: Vec<PathBuf>,
+    },
     CouldNotFindCacheDir,
     ChannelProblem(ChannelProblem),
 }
 
+impl LoadingProblem<'_> {
+    /// Problems finding or reading a module's file. Workers send these to the coordinator as
+    /// they are, and it renders them with [report_loading_problem].
+    fn is_module_file_problem(&self) -> bool {
+        matches!(
+            self,
+            LoadingProblem::FileTooLarge { .. }
+                | LoadingProblem::InvalidUtf8 { .. }
+                | LoadingProblem::AmbiguousModule { .. }
+                | LoadingProblem::FilenameCaseMismatch { .. }
+                | LoadingProblem::ModuleNotFound { .. }
+                | LoadingProblem::TriedToImportAppModule { .. }
+        )
+    }
+}
+
 #[derive(Debug)]
 pub enum ChannelProblem {
     FailedToEnqueueTask(Box<PanicReportInfo>),
@@ -966,6 +1121,7 @@ pub enum ChannelProblem {
     FailedToSendFinishedSpecializationsMsg,
     FailedToSendTaskMsg,
     FailedToSendFinishedTypeCheckingMsg,
+    FailedToSendFinishedHeadersMsg,
 }
 
 #[derive(Debug)]
@@ -983,6 +1139,12 @@ pub enum Phases {
     Monomorphize,
 }
 
+/// The channels between the coordinator and the workers are unbounded on purpose. The
+/// coordinator is the only one draining the msg channel but also sends on it (e.g. when
+/// splitting up [Msg::Many]), and in single-threaded mode it is also the only one draining
+/// the worker channel it sends [WorkerMsg::TaskAdded] on. With a bounded channel, either of
+/// those sends can block forever on a big enough project. Neither channel holds more than a
+/// few messages per task, so their memory use is bounded by the amount of work anyway.
 type MsgSender<'a> = Sender<Msg<'a>>;
 
 /// Add a task to the queue, and notify all the listeners.
@@ -1046,12 +1208,10 @@ pub fn load_and_typecheck_str<'a>(
     let cached_subs = MutMap::default();
 
     let load_config = LoadConfig {
-        target_info,
         render,
         palette,
         threading,
-        exec_mode: ExecutionMode::Check,
-        function_kind,
+        ..LoadConfig::new(target_info, function_kind, ExecutionMode::Check)
     };
 
     match load(
@@ -1062,7 +1222,7 @@ pub fn load_and_typecheck_str<'a>(
         roc_cache_dir,
         load_config,
     )? {
-        Monomorphized(_) => unreachable!(),
+        HeadersLoaded(_) | Monomorphized(_) => unreachable!(),
         TypeChecked(module) => Ok(module),
     }
 }
@@ -1079,16 +1239,80 @@ pub struct LoadStart<'a> {
     root_id: ModuleId,
     opt_platform_shorthand: Option<&'a str>,
     root_msg: Msg<'a>,
-    src_dir: PathBuf,
+    source_roots: SourceRoots,
+    source_provider: Arc<dyn SourceProvider>,
+    worker_arenas: Option<&'a mut WorkerArenas>,
+}
+
+/// The arenas the worker threads allocate in, owned by the caller so they can be reused.
+///
+/// Whatever the workers allocate ends up in the [LoadResult], so their arenas have to live as
+/// long as the main arena. By default [load] puts them in the main arena, where they are never
+/// freed. Something that loads over and over (like a watch mode or an editor) should instead keep
+/// one of these around, [reset](WorkerArenas::reset) it between loads and pass it to
+/// [LoadStart::with_worker_arenas], so the workers reuse the memory of the previous load.
+///
+/// With [Threading::Single] there are no worker threads, and everything goes in the main arena.
+#[derive(Debug, Default)]
+pub struct WorkerArenas {
+    arenas: Vec<Bump>,
+}
+
+impl WorkerArenas {
+    /// Free everything previous loads allocated, keeping the memory around for the next one.
+    pub fn reset(&mut self) {
+        for arena in self.arenas.iter_mut() {
+            arena.reset();
+        }
+    }
+
+    fn take(&mut self, count: usize) -> &mut [Bump] {
+        if self.arenas.len() < count {
+            self.arenas.resize_with(count, Bump::new);
+        }
+
+        &mut self.arenas[..count]
+    }
 }
 
 impl<'a> LoadStart<'a> {
+    /// Have the worker threads allocate in these arenas; see [WorkerArenas].
+    pub fn with_worker_arenas(self, worker_arenas: &'a mut WorkerArenas) -> Self {
+        Self {
+            worker_arenas: Some(worker_arenas),
+            ..self
+        }
+    }
+
     pub fn from_path(
         arena: &'a Bump,
         filename: PathBuf,
         render: RenderTarget,
         roc_cache_dir: RocCacheDir<'_>,
         palette: Palette,
+        max_file_size: u64,
+    ) -> Result<Self, LoadingProblem<'a>> {
+        Self::from_path_with_provider(
+            arena,
+            filename,
+            render,
+            roc_cache_dir,
+            palette,
+            max_file_size,
+            Arc::new(RealFileSystem),
+        )
+    }
+
+    /// Like [LoadStart::from_path], but read the modules through the given [SourceProvider]
+    /// instead of from the file system.
+    pub fn from_path_with_provider(
+        arena: &'a Bump,
+        filename: PathBuf,
+        render: RenderTarget,
+        roc_cache_dir: RocCacheDir<'_>,
+        palette: Palette,
+        max_file_size: u64,
+        source_provider: Arc<dyn SourceProvider>,
     ) -> Result<Self, LoadingProblem<'a>> {
         let arc_modules = Arc::new(Mutex::new(PackageModuleIds::default()));
         let root_exposed_ident_ids = IdentIds::exposed_builtins(0);
@@ -1101,6 +1325,7 @@ impl<'a> LoadStart<'a> {
 
             let res_loaded = load_filename(
                 arena,
+                &*source_provider,
                 filename,
                 true,
                 None,
@@ -1109,35 +1334,34 @@ impl<'a> LoadStart<'a> {
                 Arc::clone(&ident_ids_by_module),
                 roc_cache_dir,
                 root_start_time,
+                max_file_size,
             );
 
             match res_loaded {
                 Ok(header_output) => adjust_header_paths(header_output, &mut src_dir),
 
                 Err(problem) => {
-                    let module_ids = Arc::try_unwrap(arc_modules)
-                        .unwrap_or_else(|_| {
-                            panic!("There were still outstanding Arc references to module_ids")
-                        })
-                        .into_inner()
-                        .into_module_ids();
-
-                    let report = report_loading_problem(problem, module_ids, render, palette);
-
-                    // TODO try to gracefully recover and continue
-                    // instead of changing the control flow to exit.
-                    return Err(LoadingProblem::FormattedReport(report));
+                    return Err(report_root_problem(problem, arc_modules, render, palette));
                 }
             }
         };
 
+        let source_roots = match SourceRoots::from_dir(&*source_provider, &src_dir) {
+            Ok(source_roots) => source_roots,
+            Err(problem) => {
+                return Err(report_root_problem(problem, arc_modules, render, palette));
+            }
+        };
+
         Ok(LoadStart {
             arc_modules,
             ident_ids_by_module,
-            src_dir,
+            source_roots,
+            source_provider,
             root_id: header_output.module_id,
             root_msg: header_output.msg,
             opt_platform_shorthand: header_output.opt_platform_shorthand,
+            worker_arenas: None,
         })
     }
 
@@ -1151,6 +1375,7 @@ impl<'a> LoadStart<'a> {
         let arc_modules = Arc::new(Mutex::new(PackageModuleIds::default()));
         let root_exposed_ident_ids = IdentIds::exposed_builtins(0);
         let ident_ids_by_module = Arc::new(Mutex::new(root_exposed_ident_ids));
+        let source_provider: Arc<dyn SourceProvider> = Arc::new(RealFileSystem);
 
         // Load the root module synchronously; we can't proceed until we have its id.
         let HeaderOutput {
@@ -1162,6 +1387,7 @@ impl<'a> LoadStart<'a> {
 
             let header_output = load_from_str(
                 arena,
+                &*source_provider,
                 filename,
                 src,
                 Arc::clone(&arc_modules),
@@ -1173,17 +1399,40 @@ impl<'a> LoadStart<'a> {
             adjust_header_paths(header_output, &mut src_dir)
         };
 
+        let source_roots = SourceRoots::from_dir(&*source_provider, &src_dir)?;
+
         Ok(LoadStart {
             arc_modules,
-            src_dir,
+            source_roots,
+            source_provider,
             ident_ids_by_module,
             root_id,
             root_msg,
             opt_platform_shorthand: opt_platform_id,
+            worker_arenas: None,
         })
     }
 }
 
+/// [LoadStart::from_path] reports the problems it runs into before loading starts itself.
+fn report_root_problem<'a>(
+    problem: LoadingProblem<'a>,
+    arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
+    render: RenderTarget,
+    palette: Palette,
+) -> LoadingProblem<'a> {
+    let module_ids = Arc::try_unwrap(arc_modules)
+        .unwrap_or_else(|_| panic!("There were still outstanding Arc references to module_ids"))
+        .into_inner()
+        .into_module_ids();
+
+    let report = report_loading_problem(problem, module_ids, render, palette);
+
+    // TODO try to gracefully recover and continue
+    // instead of changing the control flow to exit.
+    LoadingProblem::FormattedReport(report)
+}
+
 fn adjust_header_paths<'a>(
     header_output: HeaderOutput<'a>,
     src_dir: &mut PathBuf,
@@ -1213,6 +1462,7 @@ fn adjust_header_paths<'a>(
 }
 
 pub enum LoadResult<'a> {
+    HeadersLoaded(LoadedHeaders),
     TypeChecked(LoadedModule),
     Monomorphized(MonomorphizedModule<'a>),
 }
@@ -1253,8 +1503,11 @@ pub enum Threading {
 ///     and report them to the coordinator thread, along with the mono::expr::Expr values of
 ///     the current function's body. At this point, we have not yet begun to assemble Procs;
 ///     all we've done is send a list of requested specializations to the coordinator.
+///     (This is [Msg::FoundSpecializations].)
 /// 11. The coordinator works through the specialization requests in parallel, adding them
-///     to a global map once they're finished. Performing one specialization may result
+///     to a global map once they're finished. (Each module reports its procs, and the
+///     requests it has for other modules, in [Msg::MadeSpecializations]. The procs end up in
+///     `State::procedures`, keyed by symbol and layout.) Performing one specialization may result
 ///     in requests for others; these are added to the queue and worked through as normal.
 ///     This process continues until *both* all modules have reported that they've finished
 ///     adding specialization requests to the queue, *and* the queue is empty (including
@@ -1311,13 +1564,9 @@ pub fn load<'a>(
             arena,
             load_start,
             exposed_types,
-            load_config.target_info,
-            load_config.function_kind,
             cached_types,
-            load_config.render,
-            load_config.palette,
-            load_config.exec_mode,
             roc_cache_dir,
+            load_config,
         ),
         Threads::Many(threads) => load_multi_threaded(
             arena,
@@ -1330,35 +1579,50 @@ pub fn load<'a>(
             load_config.palette,
             threads,
             load_config.exec_mode,
+            load_config.layer_rules,
+            load_config.progress,
+            load_config.max_file_size,
+            load_config.recover_from_syntax_errors,
             roc_cache_dir,
         ),
     }
 }
 
-/// Load using only a single thread; used when compiling to webassembly
+/// Load using only a single thread; used when compiling to webassembly.
+/// The `threading` setting of `load_config` is ignored.
 pub fn load_single_threaded<'a>(
     arena: &'a Bump,
     load_start: LoadStart<'a>,
     exposed_types: ExposedByModule,
-    target_info: TargetInfo,
-    function_kind: FunctionKind,
     cached_types: MutMap<ModuleId, TypeState>,
-    render: RenderTarget,
-    palette: Palette,
-    exec_mode: ExecutionMode,
     roc_cache_dir: RocCacheDir<'_>,
+    load_config: LoadConfig,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
+    let LoadConfig {
+        target_info,
+        render,
+        palette,
+        threading: _,
+        exec_mode,
+        function_kind,
+        layer_rules,
+        progress,
+        max_file_size,
+        recover_from_syntax_errors,
+    } = load_config;
+
     let LoadStart {
         arc_modules,
         ident_ids_by_module,
         root_id,
         root_msg,
-        src_dir,
+        source_roots,
+        source_provider,
         opt_platform_shorthand,
         ..
     } = load_start;
 
-    let (msg_tx, msg_rx) = bounded(1024);
+    let (msg_tx, msg_rx) = unbounded();
 
     msg_tx
         .send(root_msg)
@@ -1378,12 +1642,17 @@ pub fn load_single_threaded<'a>(
         palette,
         number_of_workers,
         exec_mode,
+        layer_rules,
+        progress,
+        max_file_size,
+        recover_from_syntax_errors,
+        source_roots.packages().to_vec(),
     );
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
     let injector = Injector::new();
 
-    let (worker_msg_tx, worker_msg_rx) = bounded(1024);
+    let (worker_msg_tx, worker_msg_rx) = unbounded();
     let worker_listener = worker_msg_tx;
     let worker_listeners = arena.alloc([worker_listener]);
 
@@ -1396,7 +1665,7 @@ pub fn load_single_threaded<'a>(
         match state_thread_step(
             arena,
             state,
-            &src_dir,
+            source_roots.primary(),
             worker_listeners,
             &injector,
             &msg_tx,
@@ -1417,7 +1686,8 @@ pub fn load_single_threaded<'a>(
             stealers,
             &worker_msg_rx,
             &msg_tx,
-            &src_dir,
+            &source_roots,
+            &*source_provider,
             roc_cache_dir,
             target_info,
         );
@@ -1444,6 +1714,11 @@ fn state_thread_step<'a>(
     match msg_rx.try_recv() {
         Ok(msg) => {
             match msg {
+                Msg::FinishedAllHeaders => {
+                    let headers = finish_headers(state);
+
+                    Ok(ControlFlow::Break(LoadResult::HeadersLoaded(headers)))
+                }
                 Msg::FinishedAllTypeChecking {
                     solved_subs,
                     exposed_vars_by_symbol,
@@ -1619,6 +1894,30 @@ fn state_thread_step<'a>(
     }
 }
 
+/// Tells `progress` about the problems of a module that was just type-checked.
+fn report_checked_module(progress: &dyn ProgressListener, state: &State, module_id: ModuleId) {
+    let Some((path, src)) = state.module_cache.sources.get(&module_id) else {
+        return;
+    };
+
+    let interns = Interns {
+        module_ids: state.arc_modules.lock().clone().into_module_ids(),
+        all_ident_ids: state.constrained_ident_ids.clone(),
+    };
+    let can_problems = state.module_cache.can_problems.get(&module_id);
+    let type_problems = state.module_cache.type_problems.get(&module_id);
+
+    progress.module_checked(ModuleProblems {
+        module_id,
+        path,
+        src,
+        can_problems: can_problems.map(Vec::as_slice).unwrap_or_default(),
+        type_problems: type_problems.map(Vec::as_slice).unwrap_or_default(),
+        interns: &interns,
+        imported_by: &state.module_cache.imported_by,
+    });
+}
+
 pub fn report_loading_problem(
     problem: LoadingProblem<'_>,
     module_ids: ModuleIds,
@@ -1656,6 +1955,51 @@ pub fn report_loading_problem(
         LoadingProblem::FileProblem { filename, error } => {
             to_file_problem_report_string(&filename, error)
         }
+        LoadingProblem::FileTooLarge {
+            filename,
+            size,
+            limit,
+        } => to_file_report_string(render, &palette, |alloc| {
+            to_file_too_large_report(alloc, &filename, size, limit)
+        }),
+        LoadingProblem::InvalidUtf8 {
+            filename,
+            byte_offset,
+            line,
+            column,
+        } => to_file_report_string(render, &palette, |alloc| {
+            to_invalid_utf8_report(alloc, &filename, byte_offset, line, column)
+        }),
+        LoadingProblem::AmbiguousModule {
+            module_name,
+            candidates,
+        } => to_file_report_string(render, &palette, |alloc| {
+            to_ambiguous_module_report(alloc, &module_name, &candidates)
+        }),
+        LoadingProblem::FilenameCaseMismatch { expected, found } => {
+            to_file_report_string(render, &palette, |alloc| {
+                to_filename_case_mismatch_report(alloc, &expected, &found)
+            })
+        }
+        LoadingProblem::ModuleNotFound {
+            module_name,
+            searched_paths,
+            suggestions,
+        } => to_file_report_string(render, &palette, |alloc| {
+            to_module_not_found_report(alloc, &module_name, &searched_paths, &suggestions)
+        }),
+        LoadingProblem::TriedToImportAppModule {
+            filename,
+            module_name,
+            imported_by,
+        } => to_file_report_string(render, &palette, |alloc| {
+            to_imported_app_module_report(
+                alloc,
+                &filename,
+                module_name.as_inner().as_str(),
+                &imported_by,
+            )
+        }),
         err => todo!("Loading error: {:?}", err),
     }
 }
@@ -1671,6 +2015,10 @@ fn load_multi_threaded<'a>(
     palette: Palette,
     available_threads: usize,
     exec_mode: ExecutionMode,
+    layer_rules: LayerRules,
+    progress: Option<Arc<dyn ProgressListener>>,
+    max_file_size: u64,
+    recover_from_syntax_errors: bool,
     roc_cache_dir: RocCacheDir<'_>,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
@@ -1678,12 +2026,14 @@ fn load_multi_threaded<'a>(
         ident_ids_by_module,
         root_id,
         root_msg,
-        src_dir,
+        source_roots,
+        source_provider,
         opt_platform_shorthand,
+        worker_arenas,
         ..
     } = load_start;
 
-    let (msg_tx, msg_rx) = bounded(1024);
+    let (msg_tx, msg_rx) = unbounded();
     msg_tx
         .send(root_msg)
         .map_err(|_| LoadingProblem::ChannelProblem(ChannelProblem::FailedToSendRootMsg))?;
@@ -1718,11 +2068,23 @@ fn load_multi_threaded<'a>(
         palette,
         num_workers,
         exec_mode,
+        layer_rules,
+        progress,
+        max_file_size,
+        recover_from_syntax_errors,
+        source_roots.packages().to_vec(),
     );
 
-    // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work
-    let arenas = std::iter::repeat_with(Bump::new).take(num_workers);
-    let worker_arenas = arena.alloc(bumpalo::collections::Vec::from_iter_in(arenas, arena));
+    // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work,
+    // unless the caller brought their own
+    let worker_arenas: &'a mut [Bump] = match worker_arenas {
+        Some(worker_arenas) => worker_arenas.take(num_workers),
+        None => {
+            let arenas = std::iter::repeat_with(Bump::new).take(num_workers);
+
+            bumpalo::collections::Vec::from_iter_in(arenas, arena).into_bump_slice_mut()
+        }
+    };
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
     let injector = Injector::new();
@@ -1760,14 +2122,16 @@ fn load_multi_threaded<'a>(
                 let msg_tx = msg_tx.clone();
                 let worker = worker_queues.pop().unwrap();
 
-                let (worker_msg_tx, worker_msg_rx) = bounded(1024);
+                let (worker_msg_tx, worker_msg_rx) = unbounded();
                 worker_listeners.push(worker_msg_tx);
 
                 // We only want to move a *reference* to the main task queue's
                 // injector in the thread, not the injector itself
-                // (since other threads need to reference it too). Same with src_dir.
+                // (since other threads need to reference it too). Same with source_roots
+                // and source_provider.
                 let injector = &injector;
-                let src_dir = &src_dir;
+                let source_roots = &source_roots;
+                let source_provider = &*source_provider;
 
                 // Record this thread's handle so the main thread can join it later.
                 let res_join_handle = thread_scope
@@ -1782,7 +2146,8 @@ fn load_multi_threaded<'a>(
                             stealers,
                             worker_msg_rx,
                             msg_tx,
-                            src_dir,
+                            source_roots,
+                            source_provider,
                             roc_cache_dir,
                             target_info,
                         )
@@ -1823,7 +2188,7 @@ fn load_multi_threaded<'a>(
                 match state_thread_step(
                     arena,
                     state,
-                    &src_dir,
+                    source_roots.primary(),
                     worker_listeners,
                     &injector,
                     &msg_tx,
@@ -1911,7 +2276,8 @@ fn worker_task_step<'a>(
     stealers: &[Stealer<BuildTask<'a>>],
     worker_msg_rx: &crossbeam::channel::Receiver<WorkerMsg>,
     msg_tx: &MsgSender<'a>,
-    src_dir: &Path,
+    source_roots: &SourceRoots,
+    source_provider: &dyn SourceProvider,
     roc_cache_dir: RocCacheDir<'_>,
     target_info: TargetInfo,
 ) -> Result<ControlFlow<(), ()>, LoadingProblem<'a>> {
@@ -1939,7 +2305,8 @@ fn worker_task_step<'a>(
                         let result = run_task(
                             task,
                             worker_arena,
-                            src_dir,
+                            source_roots,
+                            source_provider,
                             msg_tx.clone(),
                             roc_cache_dir,
                             target_info,
@@ -1961,6 +2328,9 @@ fn worker_task_step<'a>(
                             Err(LoadingProblem::IncorrectModuleName(err)) => {
                                 msg_tx.send(Msg::IncorrectModuleName(err)).unwrap();
                             }
+                            Err(problem) if problem.is_module_file_problem() => {
+                                msg_tx.send(Msg::FailedToLoad(problem)).unwrap();
+                            }
                             Err(other) => {
                                 return Err(other);
                             }
@@ -1985,7 +2355,8 @@ fn worker_task<'a>(
     stealers: &[Stealer<BuildTask<'a>>],
     worker_msg_rx: crossbeam::channel::Receiver<WorkerMsg>,
     msg_tx: MsgSender<'a>,
-    src_dir: &Path,
+    source_roots: &SourceRoots,
+    source_provider: &dyn SourceProvider,
     roc_cache_dir: RocCacheDir<'_>,
     target_info: TargetInfo,
 ) -> Result<(), LoadingProblem<'a>> {
@@ -2016,7 +2387,7 @@ fn worker_task<'a>(
                             BuildTask::LoadModule { module_name, .. } => {
                                 format!("BuildTask::LoadModule({module_name:?})")
                             }
-                            BuildTask::Parse { header } => {
+                            BuildTask::Parse { header, .. } => {
                                 format!("BuildTask::Parse({})", header.module_path.display())
                             }
                             BuildTask::CanonicalizeAndConstrain { parsed, .. } => format!(
@@ -2038,7 +2409,8 @@ fn worker_task<'a>(
                     let result = run_task(
                         task,
                         worker_arena,
-                        src_dir,
+                        source_roots,
+                        source_provider,
                         msg_tx.clone(),
                         roc_cache_dir,
                         target_info,
@@ -2060,6 +2432,9 @@ fn worker_task<'a>(
                         Err(LoadingProblem::IncorrectModuleName(err)) => {
                             msg_tx.send(Msg::IncorrectModuleName(err)).unwrap();
                         }
+                        Err(problem) if problem.is_module_file_problem() => {
+                            msg_tx.send(Msg::FailedToLoad(problem)).unwrap();
+                        }
                         Err(other) => {
                             return Err(other);
                         }
@@ -2164,6 +2539,64 @@ fn report_unused_imported_modules(
     }
 }
 
+/// Report imports that the project's layering rules don't allow
+fn report_forbidden_imports(state: &mut State<'_>, header: &ModuleHeader<'_>) {
+    let importer = match &header.header_type {
+        HeaderType::Interface { name, .. } | HeaderType::Hosted { name, .. } => Some(name.as_str()),
+        // apps, packages and platforms are not named by module patterns
+        HeaderType::App { .. } | HeaderType::Package { .. } | HeaderType::Platform { .. } => None,
+        HeaderType::Builtin { .. } => return,
+    };
+
+    let mut forbidden = std::vec::Vec::new();
+
+    for (pq_name, imported) in header.deps_by_name.iter() {
+        let imported_name = match pq_name {
+            PackageQualified::Unqualified(name) => name.as_str().to_string(),
+            PackageQualified::Qualified(shorthand, name) => {
+                format!("{shorthand}.{}", name.as_str())
+            }
+        };
+
+        let rule = match importer {
+            Some(importer) => state.layer_rules.forbidding(importer, &imported_name),
+            None => state.layer_rules.forbidding_unnamed(&imported_name),
+        };
+
+        if let Some(rule) = rule {
+            let region = header
+                .imported_modules
+                .get(imported)
+                .copied()
+                .unwrap_or_else(Region::zero);
+
+            forbidden.push((region, *imported, rule.to_string()));
+        }
+    }
+
+    // deps_by_name is a hash map; report in source order so output is deterministic
+    forbidden.sort_by_key(|(region, _, _)| region.start());
+
+    let existing = state
+        .module_cache
+        .can_problems
+        .entry(header.module_id)
+        .or_default();
+
+    for (region, imported, rule) in forbidden {
+        let problem = roc_problem::can::Problem::ForbiddenImport {
+            imported,
+            region,
+            rule,
+        };
+
+        // headers are re-parsed when a module is imported twice
+        if !existing.contains(&problem) {
+            existing.push(problem);
+        }
+    }
+}
+
 fn extend_header_with_builtin(header: &mut ModuleHeader, module: ModuleId) {
     header
         .package_qualified_imported_modules
@@ -2205,6 +2638,10 @@ fn update<'a>(
             log!("loaded header for {:?}", header.module_id);
             let home = header.module_id;
 
+            if let Some(progress) = &state.progress {
+                progress.module_discovered(home, &header.module_path);
+            }
+
             let mut work = MutSet::default();
 
             // Register the package's path under its shorthand
@@ -2279,6 +2716,25 @@ fn update<'a>(
                     shorthands.insert(shorthand, shorthand_path);
                 }
 
+                if header.is_root_module {
+                    for SourcePackage { name, dir } in state.source_packages.iter() {
+                        if header.packages.contains_key(name.as_str()) {
+                            continue;
+                        }
+
+                        let shorthand = &*arena.alloc_str(name);
+                        let shorthand_path = ShorthandPath::RelativeToSrc {
+                            root_module_dir: dir.clone(),
+                            root_module: dir.join("main.roc"),
+                        };
+
+                        shorthands.insert(shorthand, shorthand_path);
+
+                        // nothing to load first, so imports from it can go ahead right away
+                        work.extend(state.dependencies.notify_package(shorthand));
+                    }
+                }
+
                 match header.header_type {
                     App { to_platform, .. } => {
                         state.platform_path = PlatformPath::Valid(to_platform);
@@ -2381,6 +2837,10 @@ fn update<'a>(
                 state.module_cache.module_names.insert(*id, name.clone());
             }
 
+            if !state.layer_rules.is_empty() {
+                report_forbidden_imports(&mut state, &header);
+            }
+
             // This was a dependency. Write it down and keep processing messages.
             let mut exposed_symbols: VecSet<Symbol> = VecSet::with_capacity(header.exposes.len());
 
@@ -2397,6 +2857,10 @@ fn update<'a>(
                 .exposed_symbols_by_module
                 .insert(home, exposed_symbols);
 
+            state
+                .exposed_values
+                .insert(home, Arc::new(exposed_values_of(&header)));
+
             // add the prelude
             let mut header = header;
 
@@ -2429,6 +2893,16 @@ fn update<'a>(
                         .map(|x| *x.as_inner()),
                 );
 
+            let node = ModuleNode {
+                path: header.module_path.clone(),
+                role: header.header_type.role(),
+                imports: state.module_cache.imports[&home].clone(),
+                exposed: header.exposes.clone(),
+            };
+            state
+                .module_cache
+                .add_to_module_graph(state.root_id, home, node);
+
             let added_deps_result = state.dependencies.add_module(
                 header.module_id,
                 &header.package_qualified_imported_modules,
@@ -2455,9 +2929,15 @@ fn update<'a>(
 
             start_tasks(arena, &mut state, work, injector, worker_listeners)?;
 
+            if state.goal_phase() == Phase::LoadHeader && state.dependencies.solved_all() {
+                msg_tx.send(Msg::FinishedAllHeaders).map_err(|_| {
+                    LoadingProblem::ChannelProblem(ChannelProblem::FailedToSendFinishedHeadersMsg)
+                })?;
+            }
+
             Ok(state)
         }
-        Parsed(parsed) => {
+        Parsed(mut parsed) => {
             state
                 .module_cache
                 .sources
@@ -2465,6 +2945,26 @@ fn update<'a>(
 
             let module_id = parsed.module_id;
 
+            if !parsed.syntax_problems.is_empty() {
+                state
+                    .module_cache
+                    .can_problems
+                    .entry(module_id)
+                    .or_default()
+                    .append(&mut parsed.syntax_problems);
+            }
+
+            // Modules that import this one are canonicalized after it, so they will see these
+            if !parsed.deprecated.is_empty() {
+                let exposes = state.exposed_values.entry(module_id).or_default();
+
+                Arc::make_mut(exposes).deprecated = std::mem::take(&mut parsed.deprecated);
+            }
+
+            if let Some(progress) = &state.progress {
+                progress.module_parsed(module_id);
+            }
+
             state.module_cache.parsed.insert(module_id, parsed);
 
             let work = state.dependencies.notify(module_id, Phase::Parse);
@@ -2477,19 +2977,41 @@ fn update<'a>(
         CanonicalizedAndConstrained(CanAndCon {
             constrained_module,
             canonicalization_problems,
+            mut occurrences,
+            mut bindings,
+            doc_comments,
             module_docs,
         }) => {
             let module_id = constrained_module.module.module_id;
             log!("generated constraints for {:?}", module_id);
+            // Problems may already have been recorded for this module when its header was loaded
             state
                 .module_cache
                 .can_problems
-                .insert(module_id, canonicalization_problems);
+                .entry(module_id)
+                .or_default()
+                .extend(canonicalization_problems);
 
             if let Some(docs) = module_docs {
                 state.module_cache.documentation.insert(module_id, docs);
             }
 
+            // annotations can be canonicalized more than once, e.g. for a def and its signature
+            occurrences.sort_by_key(|occurrence| occurrence.region);
+            occurrences.dedup();
+            state
+                .module_cache
+                .occurrences
+                .insert(module_id, occurrences);
+
+            bindings.sort_by_key(|binding| binding.region);
+            bindings.dedup();
+            state.module_cache.bindings.insert(module_id, bindings);
+            state
+                .module_cache
+                .doc_comments
+                .insert(module_id, doc_comments);
+
             report_unused_imported_modules(&mut state, module_id, &constrained_module);
 
             state
@@ -2533,11 +3055,19 @@ fn update<'a>(
             log!("solved types for {:?}", module_id);
             module_timing.end_time = Instant::now();
 
+            if let Some(progress) = &state.progress {
+                progress.module_solved(module_id);
+            }
+
             state
                 .module_cache
                 .type_problems
                 .insert(module_id, solved_module.problems);
 
+            if let Some(progress) = state.progress.as_ref().filter(|p| p.wants_problems()) {
+                report_checked_module(progress.as_ref(), &state, module_id);
+            }
+
             let should_include_expects = (!loc_expects.is_empty() || !loc_dbgs.is_empty()) && {
                 let modules = state.arc_modules.lock();
                 modules
@@ -2649,7 +3179,9 @@ fn update<'a>(
                     })?;
 
                 // bookkeeping
-                state.declarations_by_id.insert(module_id, decls);
+                if state.exec_mode.keeps_declarations() {
+                    state.declarations_by_id.insert(module_id, decls);
+                }
                 state.constrained_ident_ids.insert(module_id, ident_ids);
 
                 // As far as type-checking goes, once we've solved
@@ -2688,14 +3220,16 @@ fn update<'a>(
                         .typechecked
                         .insert(module_id, typechecked);
                 } else {
-                    state.module_cache.checked.insert(
-                        module_id,
-                        CheckedModule {
-                            solved_subs,
-                            decls,
-                            abilities_store,
-                        },
-                    );
+                    if state.exec_mode.keeps_declarations() {
+                        state.module_cache.checked.insert(
+                            module_id,
+                            CheckedModule {
+                                solved_subs,
+                                decls,
+                                abilities_store,
+                            },
+                        );
+                    }
                     state.constrained_ident_ids.insert(module_id, ident_ids);
                     state.timings.insert(module_id, module_timing);
                 }
@@ -3063,10 +3597,42 @@ fn update<'a>(
                 }
             }
         }
+        Msg::FailedToLoad(mut problem) if problem.is_module_file_problem() => {
+            if let LoadingProblem::TriedToImportAppModule {
+                module_name,
+                imported_by,
+                ..
+            } = &mut problem
+            {
+                let module_id = state.arc_modules.lock().get_id(module_name);
+
+                if let Some(module_id) = module_id {
+                    let graph = &state.module_cache.module_graph;
+
+                    *imported_by = graph
+                        .dependents(module_id)
+                        .map(|importer| {
+                            let node = &graph.modules[&importer];
+
+                            (node.role, node.path.clone())
+                        })
+                        .collect();
+                    imported_by.sort_by(|(_, a), (_, b)| a.cmp(b));
+                }
+            }
+
+            let module_ids = state.arc_modules.lock().clone().into_module_ids();
+            let buf = report_loading_problem(problem, module_ids, state.render, state.palette);
+
+            Err(LoadingProblem::FormattedReport(buf))
+        }
         Msg::FailedToLoad(problem) => {
             // TODO report the error and continue instead of erroring out
             Err(problem)
         }
+        Msg::FinishedAllHeaders => {
+            unreachable!();
+        }
         Msg::FinishedAllTypeChecking { .. } => {
             unreachable!();
         }
@@ -3205,7 +3771,9 @@ fn finish_specialization<'a>(
                     platform_path,
                 })
             }
-            ExecutionMode::Check => unreachable!(),
+            ExecutionMode::HeadersOnly
+            | ExecutionMode::Check
+            | ExecutionMode::CheckProblemsOnly => unreachable!(),
         }
     }?;
 
@@ -3222,6 +3790,7 @@ fn finish_specialization<'a>(
         type_problems,
         can_problems,
         sources,
+        imported_by,
         ..
     } = module_cache;
 
@@ -3251,6 +3820,7 @@ fn finish_specialization<'a>(
         host_exposed_lambda_sets,
         entry_point,
         sources,
+        imported_by,
         timings: state.timings,
         toplevel_expects,
         glue_layouts: GlueLayouts { getters: vec![] },
@@ -3276,6 +3846,21 @@ fn proc_layout_for<'a>(
     }
 }
 
+fn finish_headers(state: State) -> LoadedHeaders {
+    let interns = Interns {
+        module_ids: state.arc_modules.lock().clone().into_module_ids(),
+        all_ident_ids: state.ident_ids_by_module.lock().clone(),
+    };
+
+    let module_graph = state.module_cache.module_graph;
+
+    LoadedHeaders {
+        root_id: state.root_id,
+        interns,
+        module_graph,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn finish(
     mut state: State,
@@ -3311,6 +3896,30 @@ fn finish(
         all_ident_ids: state.constrained_ident_ids,
     };
 
+    let module_graph = std::mem::take(&mut state.module_cache.module_graph);
+    let imported_by = std::mem::take(&mut state.module_cache.imported_by);
+
+    // the cache also holds the aliases each module imported; keep only the ones it defines
+    let aliases_by_module = std::mem::take(&mut state.module_cache.aliases)
+        .into_iter()
+        .map(|(module_id, aliases)| {
+            let defined = aliases
+                .into_iter()
+                .filter(|(symbol, _)| symbol.module_id() == module_id)
+                .map(|(symbol, (_exposed, alias))| (symbol, alias))
+                .collect();
+
+            (module_id, defined)
+        })
+        .collect();
+
+    let line_infos = state
+        .module_cache
+        .sources
+        .iter()
+        .map(|(id, (_, src))| (*id, LineInfo::new(src)))
+        .collect();
+
     let sources = state
         .module_cache
         .sources
@@ -3343,6 +3952,9 @@ fn finish(
         can_problems: state.module_cache.can_problems,
         type_problems: state.module_cache.type_problems,
         declarations_by_id,
+        occurrences: state.module_cache.occurrences,
+        bindings: state.module_cache.bindings,
+        doc_comments: state.module_cache.doc_comments,
         typechecked: state.module_cache.checked,
         dep_idents,
         exposed_aliases: exposed_aliases_by_symbol,
@@ -3351,6 +3963,10 @@ fn finish(
         exposed_types_storage,
         resolved_implementations,
         sources,
+        line_infos,
+        imported_by,
+        module_graph,
+        aliases_by_module,
         timings: state.timings,
         docs_by_module,
         abilities_store,
@@ -3360,6 +3976,7 @@ fn finish(
 /// Load a `package` or `platform` module from disk
 fn load_package_from_disk<'a>(
     arena: &'a Bump,
+    source_provider: &dyn SourceProvider,
     filename: &Path,
     shorthand: &'a str,
     app_module_id: ModuleId,
@@ -3368,13 +3985,16 @@ fn load_package_from_disk<'a>(
 ) -> Result<Msg<'a>, LoadingProblem<'a>> {
     let module_start_time = Instant::now();
     let file_io_start = module_start_time;
-    let read_result = fs::read(filename);
+    let read_result = source_provider.read_in(filename, arena);
     let file_io_duration = file_io_start.elapsed();
 
     match read_result {
-        Ok(bytes_vec) => {
+        Ok(src_bytes) => {
+            let bytes = strip_utf8_bom(src_bytes);
+
+            validate_utf8(filename, bytes)?;
+
             let parse_start = Instant::now();
-            let bytes = arena.alloc(bytes_vec);
             let parse_state = roc_parse::state::State::new(bytes);
             let parsed = roc_parse::module::parse_header(arena, parse_state.clone());
             let parse_header_duration = parse_start.elapsed();
@@ -3571,15 +4191,64 @@ fn load_builtin_module<'a>(
     Ok((module_id, Msg::Header(header)))
 }
 
+/// The values a module exposes, by name. Which of them are deprecated is only known once the
+/// module's defs are parsed.
+fn exposed_values_of(header: &ModuleHeader) -> ModuleExposes {
+    let values = header
+        .exposes
+        .iter()
+        .filter_map(|symbol| {
+            let name = header.exposed_ident_ids.get_name(symbol.ident_id())?;
+            // the same test the parser uses to tell a value's name from a type's
+            let is_value = name.starts_with(char::is_lowercase);
+
+            is_value.then(|| (Ident::from(name), *symbol))
+        })
+        .collect();
+
+    ModuleExposes {
+        values,
+        deprecated: VecMap::default(),
+    }
+}
+
+/// The exposed values of every module `module_id` imports, directly or not.
+fn transitively_imported_exposes(state: &State, module_id: ModuleId) -> ExposedValues {
+    let mut exposes = ExposedValues::default();
+    let mut visited = MutSet::default();
+    let mut stack = vec![module_id];
+
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+
+        if id != module_id {
+            if let Some(module_exposes) = state.exposed_values.get(&id) {
+                exposes.insert(id, Arc::clone(module_exposes));
+            }
+        }
+
+        if let Some(imports) = state.module_cache.imports.get(&id) {
+            stack.extend(imports.iter().copied());
+        }
+    }
+
+    exposes
+}
+
 /// Load a module by its module name, rather than by its filename
 fn load_module<'a>(
     arena: &'a Bump,
-    src_dir: &Path,
+    source_roots: &SourceRoots,
+    source_provider: &dyn SourceProvider,
+    architecture: Architecture,
     module_name: PQModuleName<'a>,
     module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
     arc_shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
     roc_cache_dir: RocCacheDir<'_>,
     ident_ids_by_module: SharedIdentIdsByModule,
+    max_file_size: u64,
 ) -> Result<HeaderOutput<'a>, LoadingProblem<'a>> {
     let module_start_time = Instant::now();
 
@@ -3629,19 +4298,113 @@ fn load_module<'a>(
         "TotallyNotJson", ModuleId::JSON
     }
 
-    let (filename, opt_shorthand) = module_name_to_path(src_dir, &module_name, arc_shorthands);
+    let (filename, opt_shorthand) = module_name_to_path(
+        source_roots,
+        source_provider,
+        architecture,
+        &module_name,
+        arc_shorthands.clone(),
+    )?;
 
-    load_filename(
+    let result = load_filename(
         arena,
+        source_provider,
         filename,
         false,
         opt_shorthand,
-        Some(module_name),
+        Some(module_name.clone()),
         module_ids,
         ident_ids_by_module,
         roc_cache_dir,
         module_start_time,
-    )
+        max_file_size,
+    );
+
+    match result {
+        Err(LoadingProblem::FileProblem {
+            filename,
+            error: io::ErrorKind::NotFound,
+        }) => Err(module_not_found(
+            source_roots,
+            source_provider,
+            architecture,
+            &module_name,
+            filename,
+            &arc_shorthands,
+        )),
+        other => other,
+    }
+}
+
+/// Say where we looked for a missing module, and which existing files the import might have meant.
+///
+/// If the file is there but spelled with different casing (e.g. `foo/bar.roc` for `Foo.Bar`),
+/// say that instead. [module_name_to_path] only checks the path it settled on, while this checks
+/// every path the module could have.
+fn module_not_found<'a>(
+    source_roots: &SourceRoots,
+    source_provider: &dyn SourceProvider,
+    architecture: Architecture,
+    module_name: &PQModuleName<'a>,
+    filename: PathBuf,
+    arc_shorthands: &Mutex<MutMap<&'a str, ShorthandPath>>,
+) -> LoadingProblem<'a> {
+    match module_name {
+        PQModuleName::Unqualified(name) => {
+            let relative_paths = module_relative_paths(architecture, name);
+
+            for candidate in relative_paths.iter() {
+                for root in source_roots.roots() {
+                    let expected = root.join(candidate);
+
+                    if let Some(found) =
+                        source_roots.case_mismatch(source_provider, root, &expected)
+                    {
+                        return LoadingProblem::FilenameCaseMismatch { expected, found };
+                    }
+                }
+            }
+
+            // suggest files close to the plain `Foo/Bar.roc`, not the architecture variant
+            let relative_path = relative_paths.last().unwrap();
+
+            LoadingProblem::ModuleNotFound {
+                module_name: name.as_str().to_string(),
+                searched_paths: source_roots.searched_paths(&relative_paths),
+                suggestions: near_misses(source_provider, source_roots.roots(), relative_path),
+            }
+        }
+        PQModuleName::Qualified(shorthand, name) => {
+            let root_module_dir = arc_shorthands
+                .lock()
+                .get(shorthand)
+                .expect("All shorthands should have been validated by now.")
+                .root_module_dir()
+                .to_path_buf();
+
+            if let Some(found) =
+                source_roots.case_mismatch(source_provider, &root_module_dir, &filename)
+            {
+                return LoadingProblem::FilenameCaseMismatch {
+                    expected: filename,
+                    found,
+                };
+            }
+
+            let suggestions = match filename.strip_prefix(&root_module_dir) {
+                Ok(relative_path) => {
+                    near_misses(source_provider, &[root_module_dir.clone()], relative_path)
+                }
+                Err(_) => Vec::new(),
+            };
+
+            LoadingProblem::ModuleNotFound {
+                module_name: format!("{shorthand}.{}", name.as_str()),
+                searched_paths: vec![filename],
+                suggestions,
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -3682,43 +4445,82 @@ impl ShorthandPath {
 }
 
 fn module_name_to_path<'a>(
-    src_dir: &Path,
+    source_roots: &SourceRoots,
+    source_provider: &dyn SourceProvider,
+    architecture: Architecture,
     module_name: &PQModuleName<'a>,
     arc_shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
-) -> (PathBuf, Option<&'a str>) {
+) -> Result<(PathBuf, Option<&'a str>), LoadingProblem<'a>> {
     let mut filename;
     let opt_shorthand;
+    let case_mismatch;
 
     match module_name {
         PQModuleName::Unqualified(name) => {
-            filename = src_dir.to_path_buf();
-
             opt_shorthand = None;
-            // Convert dots in module name to directories
-            for part in name.split(MODULE_SEPARATOR) {
-                filename.push(part);
-            }
+
+            filename = source_roots
+                .resolve(source_provider, &module_relative_paths(architecture, name))
+                .map_err(|candidates| LoadingProblem::AmbiguousModule {
+                    module_name: name.as_str().to_string(),
+                    candidates,
+                })?;
+
+            case_mismatch = source_roots
+                .roots()
+                .iter()
+                .find(|root| filename.starts_with(root))
+                .and_then(|root| source_roots.case_mismatch(source_provider, root, &filename));
         }
         PQModuleName::Qualified(shorthand, name) => {
             opt_shorthand = Some(*shorthand);
             let shorthands = arc_shorthands.lock();
-            filename = shorthands
+            let root_module_dir = shorthands
                 .get(shorthand)
                 .expect("All shorthands should have been validated by now.")
-                .root_module_dir()
-                .to_path_buf();
+                .root_module_dir();
 
-            // Convert dots in module name to directories
-            for part in name.split(MODULE_SEPARATOR) {
-                filename.push(part);
-            }
+            let [variant_path, relative_path] = module_relative_paths(architecture, name);
+            let variant_path = root_module_dir.join(variant_path);
+
+            filename = if source_provider.exists(&variant_path) {
+                variant_path
+            } else {
+                root_module_dir.join(relative_path)
+            };
+
+            case_mismatch = source_roots.case_mismatch(source_provider, root_module_dir, &filename);
         }
     }
 
-    // End with .roc
-    filename.set_extension(ROC_FILE_EXTENSION);
+    // A case-insensitive file system opens `foo/bar.roc` for `Foo.Bar`, but a case-sensitive
+    // one won't, so this is reported even when the file was found
+    if let Some(found) = case_mismatch {
+        return Err(LoadingProblem::FilenameCaseMismatch {
+            expected: filename,
+            found,
+        });
+    }
+
+    Ok((filename, opt_shorthand))
+}
+
+/// The paths a module can have under a source root or package directory, in the order they are
+/// tried.
+/// A variant for this architecture (e.g. Foo.wasm32.roc) takes precedence over Foo.roc.
+fn module_relative_paths(architecture: Architecture, name: &ModuleName) -> [PathBuf; 2] {
+    let mut relative_path = PathBuf::new();
+
+    // Convert dots in module name to directories
+    for part in name.split(MODULE_SEPARATOR) {
+        relative_path.push(part);
+    }
+
+    let architecture: &'static str = architecture.into();
+    let variant_path = relative_path.with_extension(format!("{architecture}.{ROC_FILE_EXTENSION}"));
+    relative_path.set_extension(ROC_FILE_EXTENSION);
 
-    (filename, opt_shorthand)
+    [variant_path, relative_path]
 }
 
 /// Find a task according to the following algorithm:
@@ -3756,14 +4558,21 @@ fn verify_interface_matches_file_path<'a>(
 
     let mut is_mismatched = false;
     let mut opt_path = Some(path);
-    for part in module_parts {
+    for (index, part) in module_parts.enumerate() {
         match opt_path.and_then(|path| path.file_stem().map(|fi| (path, fi))) {
             None => {
                 is_mismatched = true;
                 break;
             }
             Some((path, fi)) => {
-                if fi != part {
+                // The file itself may be a variant for one architecture, e.g. Foo.wasm32.roc
+                let is_target_variant = index == 0
+                    && match fi.to_str().and_then(|stem| stem.split_once('.')) {
+                        Some((name, arch)) => name == part && arch.parse::<Architecture>().is_ok(),
+                        None => false,
+                    };
+
+                if fi != part && !is_target_variant {
                     is_mismatched = true;
                     break;
                 }
@@ -3796,6 +4605,7 @@ struct HeaderOutput<'a> {
 
 fn parse_header<'a>(
     arena: &'a Bump,
+    source_provider: &dyn SourceProvider,
     read_file_duration: Duration,
     filename: PathBuf,
     is_root_module: bool,
@@ -3882,6 +4692,8 @@ fn parse_header<'a>(
             },
             parse_state,
         )) => {
+            verify_interface_matches_file_path(header.name, &filename, &parse_state)?;
+
             let info = HeaderInfo {
                 filename,
                 is_root_module,
@@ -3919,6 +4731,15 @@ fn parse_header<'a>(
             },
             parse_state,
         )) => {
+            // only the root module is loaded without an expected name; everything else was imported
+            if let Some(module_name) = opt_expected_module_name {
+                return Err(LoadingProblem::TriedToImportAppModule {
+                    filename,
+                    module_name,
+                    imported_by: Vec::new(),
+                });
+            }
+
             let mut app_file_dir = filename.clone();
             app_file_dir.pop();
 
@@ -3974,6 +4795,7 @@ fn parse_header<'a>(
             messages.push(Msg::Header(resolved_header));
 
             load_packages(
+                source_provider,
                 packages,
                 &mut messages,
                 roc_cache_dir,
@@ -4076,6 +4898,7 @@ fn parse_header<'a>(
 }
 
 fn load_packages<'a>(
+    source_provider: &dyn SourceProvider,
     packages: &[Loc<PackageEntry<'a>>],
     load_messages: &mut Vec<Msg<'a>>,
     roc_cache_dir: RocCacheDir,
@@ -4139,6 +4962,7 @@ fn load_packages<'a>(
 
         match load_package_from_disk(
             arena,
+            source_provider,
             &root_module_path,
             shorthand,
             module_id,
@@ -4158,6 +4982,7 @@ fn load_packages<'a>(
 /// Load a module by its filename
 fn load_filename<'a>(
     arena: &'a Bump,
+    source_provider: &dyn SourceProvider,
     filename: PathBuf,
     is_root_module: bool,
     opt_shorthand: Option<&'a str>,
@@ -4166,25 +4991,45 @@ fn load_filename<'a>(
     ident_ids_by_module: SharedIdentIdsByModule,
     roc_cache_dir: RocCacheDir<'_>,
     module_start_time: Instant,
+    max_file_size: u64,
 ) -> Result<HeaderOutput<'a>, LoadingProblem<'a>> {
     let file_io_start = Instant::now();
-    let file = fs::read(&filename);
+
+    // check the size first, so a huge file never gets read into memory
+    if let Ok(size) = source_provider.size(&filename) {
+        if size > max_file_size {
+            return Err(LoadingProblem::FileTooLarge {
+                filename,
+                size,
+                limit: max_file_size,
+            });
+        }
+    }
+
+    let file = source_provider.read_in(&filename, arena);
     let file_io_duration = file_io_start.elapsed();
 
     match file {
-        Ok(bytes) => parse_header(
-            arena,
-            file_io_duration,
-            filename,
-            is_root_module,
-            opt_shorthand,
-            opt_expected_module_name,
-            module_ids,
-            ident_ids_by_module,
-            arena.alloc(bytes),
-            roc_cache_dir,
-            module_start_time,
-        ),
+        Ok(bytes) => {
+            let bytes = strip_utf8_bom(bytes);
+
+            validate_utf8(&filename, bytes)?;
+
+            parse_header(
+                arena,
+                source_provider,
+                file_io_duration,
+                filename,
+                is_root_module,
+                opt_shorthand,
+                opt_expected_module_name,
+                module_ids,
+                ident_ids_by_module,
+                bytes,
+                roc_cache_dir,
+                module_start_time,
+            )
+        }
         Err(err) => Err(LoadingProblem::FileProblem {
             filename,
             error: err.kind(),
@@ -4192,10 +5037,45 @@ fn load_filename<'a>(
     }
 }
 
+/// Some editors (notably on Windows) start UTF-8 files with a byte order mark. It carries no
+/// information, and the parser would reject it.
+fn strip_utf8_bom(bytes: &[u8]) -> &[u8] {
+    bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes)
+}
+
+/// Check that a file read from disk is UTF-8 before handing it to the parser,
+/// so that we can point at the first bad byte.
+pub(crate) fn validate_utf8<'a, 'b>(
+    filename: &Path,
+    bytes: &'b [u8],
+) -> Result<&'b str, LoadingProblem<'a>> {
+    let byte_offset = match std::str::from_utf8(bytes) {
+        Ok(src) => return Ok(src),
+        Err(error) => error.valid_up_to(),
+    };
+
+    let valid = &bytes[..byte_offset];
+    let line_start = match valid.iter().rposition(|&b| b == b'\n') {
+        Some(newline) => newline + 1,
+        None => 0,
+    };
+
+    // Everything before `byte_offset` is valid UTF-8, so this can't fail.
+    let line_prefix = std::str::from_utf8(&valid[line_start..]).unwrap_or_default();
+
+    Err(LoadingProblem::InvalidUtf8 {
+        filename: filename.to_path_buf(),
+        byte_offset,
+        line: valid.iter().filter(|&&b| b == b'\n').count() as u32 + 1,
+        column: line_prefix.chars().count() as u32 + 1,
+    })
+}
+
 /// Load a module from a str
 /// the `filename` is never read, but used for the module name
 fn load_from_str<'a>(
     arena: &'a Bump,
+    source_provider: &dyn SourceProvider,
     filename: PathBuf,
     src: &'a str,
     module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
@@ -4208,6 +5088,7 @@ fn load_from_str<'a>(
 
     parse_header(
         arena,
+        source_provider,
         file_io_duration,
         filename,
         false,
@@ -4283,9 +5164,7 @@ fn build_header<'a>(
         HeaderType::Interface { name, .. }
         | HeaderType::Builtin { name, .. }
         | HeaderType::Hosted { name, .. } => {
-            // TODO check to see if name is consistent with filename.
-            // If it isn't, report a problem!
-
+            // parse_header has already checked that the name is consistent with the filename.
             name.as_str().into()
         }
     };
@@ -5247,6 +6126,7 @@ fn canonicalize_and_constrain<'a>(
     arena: &'a Bump,
     module_ids: &ModuleIds,
     dep_idents: IdentIdsByModule,
+    exposed_values: &ExposedValues,
     exposed_symbols: VecSet<Symbol>,
     aliases: MutMap<Symbol, Alias>,
     imported_abilities_state: PendingAbilitiesStore,
@@ -5287,6 +6167,7 @@ fn canonicalize_and_constrain<'a>(
         module_ids,
         exposed_ident_ids,
         &dep_idents,
+        exposed_values,
         aliases,
         imported_abilities_state,
         exposed_imports,
@@ -5345,6 +6226,39 @@ fn canonicalize_and_constrain<'a>(
         }
     };
 
+    let doc_comments: MutMap<Symbol, String> =
+        crate::docs::doc_comments_by_name(&parsed_defs_for_docs)
+            .into_iter()
+            .filter_map(|(name, docs)| {
+                let ident_id = module_output.scope.locals.ident_ids.get_id(name)?;
+
+                Some((Symbol::new(module_id, ident_id), docs))
+            })
+            .collect();
+
+    let mut canonicalization_problems = module_output.problems;
+
+    for occurrence in module_output.occurrences.iter() {
+        let symbol = occurrence.value;
+
+        // a module can still use what it deprecates itself
+        if symbol.module_id() == module_id {
+            continue;
+        }
+
+        let deprecated = exposed_values
+            .get(&symbol.module_id())
+            .and_then(|exposes| exposes.deprecated.get(&symbol));
+
+        if let Some(message) = deprecated {
+            canonicalization_problems.push(roc_problem::can::Problem::Deprecated {
+                symbol,
+                region: occurrence.region,
+                message: message.to_string(),
+            });
+        }
+    }
+
     // _before has an underscore because it's unused in --release builds
     let _before = roc_types::types::get_type_clone_count();
 
@@ -5434,24 +6348,55 @@ fn canonicalize_and_constrain<'a>(
 
     CanAndCon {
         constrained_module,
-        canonicalization_problems: module_output.problems,
+        canonicalization_problems,
+        occurrences: module_output.occurrences,
+        bindings: module_output.bindings,
+        doc_comments,
         module_docs,
     }
 }
 
-fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, LoadingProblem<'a>> {
+fn parse<'a>(
+    arena: &'a Bump,
+    header: ModuleHeader<'a>,
+    recover_from_syntax_errors: bool,
+) -> Result<Msg<'a>, LoadingProblem<'a>> {
     let mut module_timing = header.module_timing;
     let parse_start = Instant::now();
     let source = header.parse_state.original_bytes();
     let parse_state = header.parse_state;
-    let mut parsed_defs = match module_defs().parse(arena, parse_state.clone(), 0) {
-        Ok((_, success, _state)) => success,
-        Err((_, fail)) => {
-            return Err(LoadingProblem::ParsingFailed(
-                fail.into_file_error(header.module_path, &parse_state),
+
+    // SAFETY: Files read from disk were checked by validate_utf8 before parsing,
+    // and sources loaded from a string were a &str to begin with.
+    let src = unsafe { from_utf8_unchecked(source) };
+
+    let mut syntax_problems = std::vec::Vec::new();
+    let mut parsed_defs = if recover_from_syntax_errors {
+        // A def that fails to parse is reported, and the rest of the module is still checked
+        let (parsed_defs, syntax_errors) = module_defs_with_recovery(arena, parse_state);
+
+        for problem in syntax_errors {
+            syntax_problems.push(recovered_syntax_problem(
+                header.module_id,
+                src,
+                header.module_path.clone(),
+                problem.region,
+                &problem.value,
             ));
         }
+
+        parsed_defs
+    } else {
+        match module_defs().parse(arena, parse_state.clone(), 0) {
+            Ok((_, success, _state)) => success,
+            Err((_, fail)) => {
+                return Err(LoadingProblem::ParsingFailed(
+                    fail.into_file_error(header.module_path, &parse_state),
+                ));
+            }
+        }
     };
+
     for value in header.defined_values.into_iter() {
         // TODO: should these have a region?
         parsed_defs.push_value_def(value, Region::zero(), &[], &[]);
@@ -5465,10 +6410,18 @@ fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, Loadi
 
     let imported_modules = header.imported_modules;
 
-    // SAFETY: By this point we've already incrementally verified that there
-    // are no UTF-8 errors in these bytes. If there had been any UTF-8 errors,
-    // we'd have bailed out before now.
-    let src = unsafe { from_utf8_unchecked(source) };
+    let deprecated = crate::docs::deprecations_by_name(&parsed_defs)
+        .into_iter()
+        .filter_map(|(name, message)| {
+            let ident_id = header.exposed_ident_ids.get_id(name)?;
+            let symbol = Symbol::new(header.module_id, ident_id);
+
+            header
+                .exposes
+                .contains(&symbol)
+                .then(|| (symbol, message.into()))
+        })
+        .collect();
 
     let ModuleHeader {
         module_id,
@@ -5495,6 +6448,8 @@ fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, Loadi
         symbols_from_requires,
         header_type,
         header_comments: header_docs,
+        deprecated,
+        syntax_problems,
     };
 
     Ok(Msg::Parsed(parsed))
@@ -6277,7 +7232,8 @@ fn load_derived_partial_procs<'a>(
 fn run_task<'a>(
     task: BuildTask<'a>,
     arena: &'a Bump,
-    src_dir: &Path,
+    source_roots: &SourceRoots,
+    source_provider: &dyn SourceProvider,
     msg_tx: MsgSender<'a>,
     roc_cache_dir: RocCacheDir<'_>,
     target_info: TargetInfo,
@@ -6290,21 +7246,29 @@ fn run_task<'a>(
             module_ids,
             shorthands,
             ident_ids_by_module,
+            max_file_size,
         } => load_module(
             arena,
-            src_dir,
+            source_roots,
+            source_provider,
+            target_info.architecture,
             module_name,
             module_ids,
             shorthands,
             roc_cache_dir,
             ident_ids_by_module,
+            max_file_size,
         )
         .map(|HeaderOutput { msg, .. }| msg),
-        Parse { header } => parse(arena, header),
+        Parse {
+            header,
+            recover_from_syntax_errors,
+        } => parse(arena, header, recover_from_syntax_errors),
         CanonicalizeAndConstrain {
             parsed,
             module_ids,
             dep_idents,
+            exposed_values,
             exposed_symbols,
             aliases,
             abilities_store,
@@ -6315,6 +7279,7 @@ fn run_task<'a>(
                 arena,
                 &module_ids,
                 dep_idents,
+                &exposed_values,
                 exposed_symbols,
                 aliases,
                 abilities_store,
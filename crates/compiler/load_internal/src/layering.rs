@@ -0,0 +1,229 @@
+//! Architectural layering rules for the module import graph.
+//!
+//! Large projects often want guardrails like "nothing under `Ui` may import anything under `Db`".
+//! [LayerRules] lists such forbidden edges, and the loader checks every module's imports against
+//! them as soon as its header has been parsed.
+//!
+//! Rules are written one per line, for example:
+//!
+//! ```text
+//! # presentation code must go through the service layer
+//! Ui.* may not import Db.*
+//! Db.* may not import Ui.*
+//! ```
+//!
+//! A pattern is either an exact module name (`Ui.Button`), a module name followed by `.*`
+//! (matching that module and everything nested under it), or `*` (matching every module).
+//! Modules from packages are written with the package's shorthand, as in `pf.Stdout` or `pf.*`.
+//!
+//! Apps, packages and platforms have no module name for a pattern to match, so only rules for
+//! `*` cover their imports. Builtins import each other and are never checked.
+use std::fmt;
+use std::path::Path;
+
+use crate::file::{validate_utf8, LoadingProblem};
+use crate::source_provider::SourceProvider;
+
+/// The file the CLI looks for next to the root module to find a project's layering rules.
+pub const LAYER_RULES_FILENAME: &str = "roc-layers.txt";
+
+const FORBIDS: &str = "may not import";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayerRules {
+    rules: Vec<LayerRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerRule {
+    pub importer: ModulePattern,
+    pub imported: ModulePattern,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModulePattern {
+    /// `*`
+    Any,
+    /// e.g. `Ui.*`, which matches `Ui`, `Ui.Button`, `Ui.Button.Style`, ...
+    Nested(String),
+    /// e.g. `Ui.Button`
+    Exact(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerRulesError {
+    /// 1-based line number of the offending rule
+    pub line: usize,
+    pub message: String,
+}
+
+impl LayerRules {
+    pub fn new(rules: Vec<LayerRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn rules(&self) -> &[LayerRule] {
+        &self.rules
+    }
+
+    pub fn parse(src: &str) -> Result<Self, LayerRulesError> {
+        let mut rules = Vec::new();
+
+        for (index, line) in src.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((before_comment, _)) => before_comment,
+                None => line,
+            }
+            .trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = |message: String| LayerRulesError {
+                line: index + 1,
+                message,
+            };
+
+            let (importer, imported) = line.split_once(FORBIDS).ok_or_else(|| {
+                error(format!(
+                    "expected a rule of the form `Ui.* {FORBIDS} Db.*`, but got `{line}`"
+                ))
+            })?;
+
+            rules.push(LayerRule {
+                importer: ModulePattern::parse(importer).map_err(error)?,
+                imported: ModulePattern::parse(imported).map_err(error)?,
+            });
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Read the rules from [LAYER_RULES_FILENAME] in the given directory.
+    /// A missing file means there are no rules.
+    pub fn from_dir<'a>(
+        source_provider: &dyn SourceProvider,
+        dir: &Path,
+    ) -> Result<Self, LoadingProblem<'a>> {
+        let path = dir.join(LAYER_RULES_FILENAME);
+
+        match source_provider.read(&path) {
+            Ok(bytes) => {
+                let src = validate_utf8(&path, &bytes)?;
+
+                Self::parse(src).map_err(|err| LoadingProblem::FormattedReport(err.to_string()))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(LoadingProblem::FileProblem {
+                filename: path,
+                error: err.kind(),
+            }),
+        }
+    }
+
+    /// The rules of the project whose root module is at the given path; they live next to it.
+    pub fn for_root_module<'a>(
+        source_provider: &dyn SourceProvider,
+        root_module: &Path,
+    ) -> Result<Self, LoadingProblem<'a>> {
+        match root_module.parent() {
+            Some(dir) => Self::from_dir(source_provider, dir),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// The first rule that forbids `importer` from importing `imported`, if any.
+    pub fn forbidding(&self, importer: &str, imported: &str) -> Option<&LayerRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.importer.matches(importer) && rule.imported.matches(imported))
+    }
+
+    /// Like [LayerRules::forbidding], for an importer without a module name, like an app.
+    /// Only rules for every importer (`*`) apply to it.
+    pub fn forbidding_unnamed(&self, imported: &str) -> Option<&LayerRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.importer == ModulePattern::Any && rule.imported.matches(imported))
+    }
+}
+
+impl ModulePattern {
+    fn parse(src: &str) -> Result<Self, String> {
+        let src = src.trim();
+
+        let name = match src {
+            "*" => return Ok(ModulePattern::Any),
+            _ => src.strip_suffix(".*").unwrap_or(src),
+        };
+
+        let is_module_name = |name: &str| {
+            !name.is_empty()
+                && name.split('.').all(|part| {
+                    part.starts_with(|c: char| c.is_ascii_uppercase())
+                        && part.chars().all(|c| c.is_ascii_alphanumeric())
+                })
+        };
+
+        let is_shorthand = |name: &str| {
+            name.starts_with(|c: char| c.is_ascii_lowercase())
+                && name.chars().all(|c| c.is_ascii_alphanumeric())
+        };
+
+        let is_valid = match name.split_once('.') {
+            Some((shorthand, module_name)) if is_shorthand(shorthand) => {
+                is_module_name(module_name)
+            }
+            // `pf.*` matches every module of the `pf` package
+            _ => is_module_name(name) || (is_shorthand(name) && name.len() < src.len()),
+        };
+
+        if !is_valid {
+            return Err(format!("`{src}` is not a module name or module pattern"));
+        }
+
+        if name.len() == src.len() {
+            Ok(ModulePattern::Exact(name.to_string()))
+        } else {
+            Ok(ModulePattern::Nested(name.to_string()))
+        }
+    }
+
+    pub fn matches(&self, module_name: &str) -> bool {
+        match self {
+            ModulePattern::Any => true,
+            ModulePattern::Exact(name) => module_name == name,
+            ModulePattern::Nested(prefix) => match module_name.strip_prefix(prefix.as_str()) {
+                Some(rest) => rest.is_empty() || rest.starts_with('.'),
+                None => false,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ModulePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModulePattern::Any => f.write_str("*"),
+            ModulePattern::Nested(prefix) => write!(f, "{prefix}.*"),
+            ModulePattern::Exact(name) => f.write_str(name),
+        }
+    }
+}
+
+impl fmt::Display for LayerRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {FORBIDS} {}", self.importer, self.imported)
+    }
+}
+
+impl fmt::Display for LayerRulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{LAYER_RULES_FILENAME}:{}: {}", self.line, self.message)
+    }
+}
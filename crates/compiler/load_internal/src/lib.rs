@@ -6,8 +6,12 @@
 use roc_module::symbol::ModuleId;
 pub mod docs;
 pub mod file;
+pub mod layering;
 pub mod module;
 mod module_cache;
+pub mod progress;
+pub mod source_provider;
+pub mod source_roots;
 mod work;
 
 #[cfg(target_family = "wasm")]
@@ -14,10 +14,11 @@ use roc_module::symbol::{
 use roc_mono::ir::{GlueLayouts, HostExposedLambdaSets, LambdaSetId, Proc, ProcLayout, ProcsBase};
 use roc_mono::layout::{LayoutCache, STLayoutInterner};
 use roc_parse::ast::{CommentOrNewline, Defs, TypeAnnotation, ValueDef};
-use roc_parse::header::{HeaderType, PackageName};
-use roc_region::all::{Loc, Region};
+use roc_parse::header::{HeaderRole, HeaderType, PackageName};
+use roc_region::all::{LineInfo, Loc, Region};
 use roc_solve::module::Solved;
 use roc_solve_problem::TypeError;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
 use roc_types::subs::{ExposedTypesStorageSubs, Subs, VarStore, Variable};
 use roc_types::types::{Alias, Types};
 use std::path::PathBuf;
@@ -35,13 +36,31 @@ pub struct LoadedModule {
     pub can_problems: MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     pub type_problems: MutMap<ModuleId, Vec<TypeError>>,
     pub declarations_by_id: MutMap<ModuleId, Declarations>,
+    /// Every place each module refers to a value, type or opaque by name, in source order.
+    /// The places that define them are in `bindings`.
+    pub occurrences: MutMap<ModuleId, Vec<Loc<Symbol>>>,
+    /// Every place each module binds a name, in source order: top-level and nested defs,
+    /// function arguments, and names bound by destructures and `when` branches.
+    pub bindings: MutMap<ModuleId, Vec<Loc<Symbol>>>,
+    /// The `##` doc comment of each top-level def that has one, exposed or not
+    pub doc_comments: MutMap<ModuleId, MutMap<Symbol, String>>,
     pub exposed_to_host: MutMap<Symbol, Variable>,
     pub dep_idents: IdentIdsByModule,
     pub exposed_aliases: MutMap<Symbol, Alias>,
+    /// The type aliases and opaque types each module defines, with their type parameters and
+    /// definitions, whether or not the module exposes them.
+    pub aliases_by_module: MutMap<ModuleId, MutMap<Symbol, Alias>>,
     pub exposed_values: Vec<Symbol>,
     pub exposed_types_storage: ExposedTypesStorageSubs,
     pub resolved_implementations: ResolvedImplementations,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
+    /// Where each line starts in the corresponding entry of `sources`, for turning a module's
+    /// `Region`s into lines and columns.
+    pub line_infos: MutMap<ModuleId, LineInfo>,
+    /// For every transitively imported module, the module that imports it on the shortest import
+    /// chain from the root module. Used to explain why a module was loaded at all.
+    pub imported_by: MutMap<ModuleId, ModuleId>,
+    pub module_graph: ModuleGraph,
     pub timings: MutMap<ModuleId, ModuleTiming>,
     pub docs_by_module: Vec<(ModuleId, ModuleDocumentation)>,
     pub abilities_store: AbilitiesStore,
@@ -63,6 +82,41 @@ impl LoadedModule {
         total
     }
 
+    /// The modules through which `module_id` was imported, nearest importer first and ending
+    /// with the root module. This is empty for the root module itself.
+    pub fn import_chain(&self, module_id: ModuleId) -> Vec<ModuleId> {
+        import_chain(&self.imported_by, module_id)
+    }
+
+    /// The type of every top-level value in the checked modules, printed the way `roc check`
+    /// would print it. Types are only printed when asked for, so loads that just check for
+    /// problems don't pay for them.
+    pub fn inferred_types(&mut self) -> MutMap<Symbol, String> {
+        let mut inferred_types = MutMap::default();
+
+        if let Some(declarations) = self.declarations_by_id.get(&self.module_id) {
+            record_inferred_types(
+                self.module_id,
+                declarations,
+                self.solved.inner_mut(),
+                &self.interns,
+                &mut inferred_types,
+            );
+        }
+
+        for (module_id, checked) in self.typechecked.iter_mut() {
+            record_inferred_types(
+                *module_id,
+                &checked.decls,
+                checked.solved_subs.inner_mut(),
+                &self.interns,
+                &mut inferred_types,
+            );
+        }
+
+        inferred_types
+    }
+
     pub fn exposed_values_str(&self) -> Vec<&str> {
         self.exposed_values
             .iter()
@@ -78,6 +132,101 @@ impl LoadedModule {
     }
 }
 
+/// Print the type of each top-level value in `declarations`. Printing names the type variables
+/// in `subs`, so it is rolled back afterwards and the module's types stay as the solver left them.
+fn record_inferred_types(
+    home: ModuleId,
+    declarations: &Declarations,
+    subs: &mut Subs,
+    interns: &Interns,
+    inferred_types: &mut MutMap<Symbol, String>,
+) {
+    use roc_can::expr::DeclarationTag::*;
+
+    let snapshot = subs.snapshot();
+
+    for (index, tag) in declarations.declarations.iter().enumerate() {
+        match tag {
+            Value | Function(_) | Recursive(_) | TailRecursive(_) => {
+                let symbol = declarations.symbols[index].value;
+                let var = declarations.variables[index];
+                let type_str = name_and_print_var(var, subs, home, interns, DebugPrint::NOTHING);
+
+                inferred_types.insert(symbol, type_str);
+            }
+            Destructure(d_index) => {
+                let pattern_vars = &declarations.destructs[d_index.index()].pattern_vars;
+
+                for (symbol, var) in pattern_vars.iter() {
+                    let type_str =
+                        name_and_print_var(*var, subs, home, interns, DebugPrint::NOTHING);
+
+                    inferred_types.insert(*symbol, type_str);
+                }
+            }
+            // the defs of a cycle follow its marker, and expectations don't define anything
+            MutualRecursion { .. } | Expectation | ExpectationFx => {}
+        }
+    }
+
+    subs.rollback_to(snapshot);
+}
+
+/// The result of [crate::file::ExecutionMode::HeadersOnly]: every module in the dependency tree,
+/// loaded only as far as its header. This is cheap enough for editors and doc tools to index a
+/// whole project.
+#[derive(Debug)]
+pub struct LoadedHeaders {
+    pub root_id: ModuleId,
+    /// The names of all modules, and of the symbols they expose
+    pub interns: Interns,
+    pub module_graph: ModuleGraph,
+}
+
+/// Every module that was loaded from a file, with its direct imports, what it exposes,
+/// and where it was loaded from.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    pub modules: MutMap<ModuleId, ModuleNode>,
+}
+
+#[derive(Debug)]
+pub struct ModuleNode {
+    pub path: PathBuf,
+    pub role: HeaderRole,
+    pub imports: MutSet<ModuleId>,
+    /// In the order they are listed in the module's header
+    pub exposed: Vec<Symbol>,
+}
+
+impl ModuleGraph {
+    /// The modules that directly import `module_id`
+    pub fn dependents(&self, module_id: ModuleId) -> impl Iterator<Item = ModuleId> + '_ {
+        self.modules
+            .iter()
+            .filter(move |(_, node)| node.imports.contains(&module_id))
+            .map(|(id, _)| *id)
+    }
+}
+
+/// The modules through which `module_id` was imported, nearest importer first and ending with the
+/// root module, following edges like the ones in [LoadedModule::imported_by].
+/// This is empty for the root module itself.
+pub fn import_chain(
+    imported_by: &MutMap<ModuleId, ModuleId>,
+    module_id: ModuleId,
+) -> Vec<ModuleId> {
+    let mut chain = Vec::new();
+    let mut current = module_id;
+
+    while let Some(importer) = imported_by.get(&current) {
+        chain.push(*importer);
+        current = *importer;
+    }
+
+    chain
+}
+
 #[derive(Debug)]
 pub(crate) struct ModuleHeader<'a> {
     pub(crate) module_id: ModuleId,
@@ -177,6 +326,12 @@ pub struct MonomorphizedModule<'a> {
     pub entry_point: EntryPoint<'a>,
     pub exposed_to_host: ExposedToHost,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
+    /// Where each line starts in the corresponding entry of `sources`, for turning a module's
+    /// `Region`s into lines and columns.
+    pub line_infos: MutMap<ModuleId, LineInfo>,
+    /// For every transitively imported module, the module that imports it on the shortest import
+    /// chain from the root module. Used to explain why a module was loaded at all.
+    pub imported_by: MutMap<ModuleId, ModuleId>,
     pub timings: MutMap<ModuleId, ModuleTiming>,
     pub expectations: VecMap<ModuleId, Expectations>,
     pub uses_prebuilt_platform: bool,
@@ -197,6 +352,10 @@ pub struct ParsedModule<'a> {
     pub symbols_from_requires: Vec<(Loc<Symbol>, Loc<TypeAnnotation<'a>>)>,
     pub header_type: HeaderType<'a>,
     pub header_comments: &'a [CommentOrNewline<'a>],
+    /// The exposed values and types whose doc comment marks them as deprecated
+    pub deprecated: VecMap<Symbol, Box<str>>,
+    /// The defs that failed to parse, which are missing from `parsed_defs`
+    pub syntax_problems: Vec<roc_problem::can::Problem>,
 }
 
 #[derive(Debug)]
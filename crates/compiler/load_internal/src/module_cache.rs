@@ -1,7 +1,7 @@
 use crate::docs::ModuleDocumentation;
 use crate::module::{
     CheckedModule, ConstrainedModule, FoundSpecializationsModule, LateSpecializationsModule,
-    ModuleHeader, ParsedModule, TypeCheckedModule,
+    ModuleGraph, ModuleHeader, ModuleNode, ParsedModule, TypeCheckedModule,
 };
 use roc_can::abilities::PendingAbilitiesStore;
 use roc_collections::{MutMap, MutSet, VecMap};
@@ -9,14 +9,22 @@ use roc_module::ident::ModuleName;
 use roc_module::symbol::{ModuleId, PQModuleName, Symbol};
 use roc_mono::ir::ExternalSpecializations;
 use roc_problem::Severity;
+use roc_region::all::Loc;
 use roc_solve_problem::TypeError;
 use roc_types::types::Alias;
+use std::collections::VecDeque;
 use std::path::PathBuf;
 
 /// Struct storing various intermediate stages by their ModuleId
 #[derive(Debug)]
 pub(crate) struct ModuleCache<'a> {
     pub(crate) module_names: MutMap<ModuleId, PQModuleName<'a>>,
+    /// Every module whose header has been loaded so far
+    pub(crate) module_graph: ModuleGraph,
+    /// Like [crate::module::LoadedModule::imported_by], for the headers loaded so far
+    pub(crate) imported_by: MutMap<ModuleId, ModuleId>,
+    /// How many imports away from the root each module in `imported_by` is
+    import_depths: MutMap<ModuleId, usize>,
 
     /// Phases
     pub(crate) headers: MutMap<ModuleId, ModuleHeader<'a>>,
@@ -37,6 +45,9 @@ pub(crate) struct ModuleCache<'a> {
     pub(crate) documentation: VecMap<ModuleId, ModuleDocumentation>,
     pub(crate) can_problems: MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     pub(crate) type_problems: MutMap<ModuleId, Vec<TypeError>>,
+    pub(crate) occurrences: MutMap<ModuleId, Vec<Loc<Symbol>>>,
+    pub(crate) bindings: MutMap<ModuleId, Vec<Loc<Symbol>>>,
+    pub(crate) doc_comments: MutMap<ModuleId, MutMap<Symbol, String>>,
 
     pub(crate) sources: MutMap<ModuleId, (PathBuf, &'a str)>,
 }
@@ -59,6 +70,53 @@ impl<'a> ModuleCache<'a> {
     pub fn has_errors(&self) -> bool {
         self.has_can_errors() || self.has_type_errors()
     }
+
+    /// Adds a module whose header was just loaded to `module_graph`, and updates `imported_by`
+    /// for the modules it imports, directly or not.
+    ///
+    /// Headers come in whatever order the workers finish them, so an import chain found
+    /// earlier can turn out not to be the shortest. Between chains of the same length, the
+    /// importer with the smaller path wins, so the result doesn't depend on that order either.
+    pub(crate) fn add_to_module_graph(
+        &mut self,
+        root: ModuleId,
+        module_id: ModuleId,
+        node: ModuleNode,
+    ) {
+        self.module_graph.modules.insert(module_id, node);
+
+        let mut queue = VecDeque::from([module_id]);
+
+        while let Some(importer) = queue.pop_front() {
+            let depth = match self.import_depths.get(&importer) {
+                _ if importer == root => 0,
+                Some(depth) => *depth,
+                // not reachable from the root yet; its imports are updated once it is
+                None => continue,
+            };
+            let Some(node) = self.module_graph.modules.get(&importer) else {
+                continue;
+            };
+
+            for imported in node.imports.iter().filter(|imported| **imported != root) {
+                let known_depth = self.import_depths.get(imported).copied();
+
+                if known_depth.map_or(true, |known_depth| depth + 1 < known_depth) {
+                    self.import_depths.insert(*imported, depth + 1);
+                    self.imported_by.insert(*imported, importer);
+
+                    // its own imports may now have a shorter chain through it
+                    queue.push_back(*imported);
+                } else if known_depth == Some(depth + 1) {
+                    let known_importer = &self.module_graph.modules[&self.imported_by[imported]];
+
+                    if node.path < known_importer.path {
+                        self.imported_by.insert(*imported, importer);
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl Default for ModuleCache<'_> {
@@ -92,6 +150,9 @@ impl Default for ModuleCache<'_> {
 
         Self {
             module_names,
+            module_graph: Default::default(),
+            imported_by: Default::default(),
+            import_depths: Default::default(),
             headers: Default::default(),
             parsed: Default::default(),
             aliases: Default::default(),
@@ -107,6 +168,9 @@ impl Default for ModuleCache<'_> {
             documentation: Default::default(),
             can_problems: Default::default(),
             type_problems: Default::default(),
+            occurrences: Default::default(),
+            bindings: Default::default(),
+            doc_comments: Default::default(),
             sources: Default::default(),
         }
     }
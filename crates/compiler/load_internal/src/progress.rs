@@ -0,0 +1,58 @@
+//! Callbacks for following a load as it happens.
+//!
+//! Loading a big project can take a while, and the loader is otherwise silent until it either
+//! finishes or fails. A [ProgressListener] passed in [crate::file::LoadConfig] is told about each
+//! module as it moves through the pipeline, so the CLI can draw a progress bar and an editor can
+//! show per-module status.
+//!
+//! The callbacks are made from the coordinator thread, in the order the coordinator sees the
+//! events. They should return quickly, because no other work is handed out while they run.
+use roc_collections::MutMap;
+use roc_module::symbol::{Interns, ModuleId};
+use roc_solve_problem::TypeError;
+use std::fmt;
+use std::path::Path;
+
+/// Every method has a default that does nothing, so implementations only need the ones they
+/// care about.
+pub trait ProgressListener: Send + Sync {
+    /// The module's header was loaded. Its imports will be discovered next, so the number of
+    /// modules reported here keeps growing until all headers are in.
+    fn module_discovered(&self, _module_id: ModuleId, _path: &Path) {}
+
+    /// The rest of the module was parsed.
+    fn module_parsed(&self, _module_id: ModuleId) {}
+
+    /// The module was type-checked. This is the last step for [crate::file::ExecutionMode::Check].
+    fn module_solved(&self, _module_id: ModuleId) {}
+
+    /// Whether [ProgressListener::module_checked] should be called. Gathering what it is given
+    /// copies the names of every module checked so far, so this is off unless asked for.
+    fn wants_problems(&self) -> bool {
+        false
+    }
+
+    /// The module was type-checked, and these are all of its problems. Called right after
+    /// [ProgressListener::module_solved], if [ProgressListener::wants_problems].
+    fn module_checked(&self, _module: ModuleProblems<'_>) {}
+}
+
+/// A module that was just type-checked, with what it takes to report its problems.
+pub struct ModuleProblems<'a> {
+    pub module_id: ModuleId,
+    pub path: &'a Path,
+    pub src: &'a str,
+    pub can_problems: &'a [roc_problem::can::Problem],
+    pub type_problems: &'a [TypeError],
+    /// The names in this module, in the modules it imports, and in any other module checked
+    /// so far
+    pub interns: &'a Interns,
+    /// Like [crate::module::LoadedModule::imported_by], for the modules loaded so far
+    pub imported_by: &'a MutMap<ModuleId, ModuleId>,
+}
+
+impl fmt::Debug for dyn ProgressListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressListener")
+    }
+}
@@ -0,0 +1,108 @@
+//! Where the loader reads module sources from.
+//!
+//! Normally that is the file system, but tests can load an in-memory tree of modules, an editor
+//! can overlay the buffers the user hasn't saved yet, and a package mirror could serve sources
+//! straight out of an archive. Pass a [SourceProvider] to
+//! [crate::file::LoadStart::from_path_with_provider] to load through it.
+//!
+//! The provider is asked for `.roc` modules (including the root module and the root modules of
+//! packages) and for the source roots file. When an import can't be found, the loader lists
+//! directories through it to suggest what the import might have meant. Files brought in with
+//! `imports ["file.txt" as ...]` are still read from disk.
+use bumpalo::Bump;
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Workers call into the provider from several threads at once.
+pub trait SourceProvider: Send + Sync {
+    /// The contents of the file. An in-memory provider can hand out its own bytes without
+    /// copying them.
+    fn read(&self, path: &Path) -> io::Result<Cow<'_, [u8]>>;
+
+    /// The contents of the file, in `arena`, which is where the loader keeps sources while it
+    /// parses them. The default copies what [SourceProvider::read] returns.
+    fn read_in<'a>(&self, path: &Path, arena: &'a Bump) -> io::Result<&'a [u8]> {
+        self.read(path)
+            .map(|bytes| &*arena.alloc_slice_copy(&bytes))
+    }
+
+    /// Whether there is a file (not a directory) at this path.
+    fn exists(&self, path: &Path) -> bool;
+
+    /// When the file last changed, for callers that cache what they loaded.
+    fn modified_time(&self, path: &Path) -> io::Result<SystemTime>;
+
+    /// What is directly inside this directory, in no particular order.
+    fn list_dir(&self, dir: &Path) -> io::Result<Vec<DirEntry>>;
+
+    /// The size of the file in bytes. The loader checks this before reading, so a huge file
+    /// never gets read into memory; the default reads the file to find out.
+    fn size(&self, path: &Path) -> io::Result<u64> {
+        self.read(path).map(|bytes| bytes.len() as u64)
+    }
+}
+
+/// A file or directory [SourceProvider::list_dir] found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    pub name: OsString,
+    pub is_dir: bool,
+}
+
+impl fmt::Debug for dyn SourceProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SourceProvider")
+    }
+}
+
+/// Reads everything from the file system. This is what [crate::file::LoadStart::from_path] uses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealFileSystem;
+
+impl SourceProvider for RealFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Cow<'_, [u8]>> {
+        std::fs::read(path).map(Cow::Owned)
+    }
+
+    fn read_in<'a>(&self, path: &Path, arena: &'a Bump) -> io::Result<&'a [u8]> {
+        use std::io::Read;
+
+        let mut file = std::fs::File::open(path)?;
+        // straight into the arena, rather than into a Vec that would then be copied there.
+        // Like std::fs::read, this reads as much as the file had when it was opened.
+        let bytes = arena.alloc_slice_fill_copy(file.metadata()?.len() as usize, 0);
+
+        file.read_exact(bytes)?;
+
+        Ok(bytes)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn modified_time(&self, path: &Path) -> io::Result<SystemTime> {
+        std::fs::metadata(path)?.modified()
+    }
+
+    fn list_dir(&self, dir: &Path) -> io::Result<Vec<DirEntry>> {
+        std::fs::read_dir(dir)?
+            .map(|entry| {
+                let entry = entry?;
+
+                Ok(DirEntry {
+                    name: entry.file_name(),
+                    is_dir: entry.file_type()?.is_dir(),
+                })
+            })
+            .collect()
+    }
+
+    fn size(&self, path: &Path) -> io::Result<u64> {
+        Ok(std::fs::metadata(path)?.len())
+    }
+}
@@ -0,0 +1,336 @@
+//! The directories the loader searches for unqualified imports.
+//!
+//! By default `import Foo.Bar` resolves to `Foo/Bar.roc` under the root module's directory.
+//! Projects that keep code in several trees (for example generated code next to hand-written
+//! code) can list extra directories in [SOURCE_ROOTS_FILENAME] next to the root module:
+//!
+//! ```text
+//! # relative to this file
+//! generated
+//! ../shared/src
+//!
+//! # named packages
+//! json: ../vendor/json/src
+//! ```
+//!
+//! Roots are searched in order, starting with the root module's own directory. A module name
+//! that resolves to a file in more than one root is an error rather than a silent shadowing.
+//!
+//! A line like `json: ../vendor/json/src` names a package instead: `import json.Decode` then
+//! resolves to `Decode.roc` in that directory, as if the app header had `packages { json: ... }`.
+//! A package of the same name in the root module's header takes precedence.
+//!
+//! Within the roots, a module can have variants for specific architectures, like
+//! `Foo/Bar.wasm32.roc` next to `Foo/Bar.roc`. When loading for that architecture the variant
+//! is used, and the other files are never read.
+//!
+//! On a case-insensitive file system, `import Foo.Bar` also finds `foo/bar.roc`, and the same
+//! project then fails to load on another machine. [SourceRoots::case_mismatch] compares every
+//! module's path with the directory listings, so this is reported on any file system, and the
+//! report names the file that is spelled wrong.
+//!
+//! When a module can't be found at all, [near_misses] looks through the roots for the file the
+//! import probably meant, so the report can suggest it.
+//!
+use crate::file::{validate_utf8, LoadingProblem};
+use crate::source_provider::SourceProvider;
+use parking_lot::Mutex;
+use roc_collections::MutMap;
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The file the loader looks for next to the root module to find extra source roots.
+pub const SOURCE_ROOTS_FILENAME: &str = "roc-roots.txt";
+
+/// How many directories deep [near_misses] looks under each root
+const MAX_SEARCH_DEPTH: usize = 4;
+
+/// How many files [near_misses] looks at in total, so a huge tree can't stall the error report
+const MAX_FILES_SEARCHED: usize = 10_000;
+
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Debug)]
+pub struct SourceRoots {
+    /// Always starts with the root module's directory
+    roots: Vec<PathBuf>,
+    packages: Vec<SourcePackage>,
+    /// The names in each directory [SourceRoots::case_mismatch] has listed, or `None` if it
+    /// could not be listed. Every module is checked, so each directory is only listed once.
+    listings: Mutex<MutMap<PathBuf, Option<Arc<[OsString]>>>>,
+}
+
+/// A package named in [SOURCE_ROOTS_FILENAME], e.g. `json: ../vendor/json/src`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePackage {
+    /// The shorthand imports use, e.g. `json` in `import json.Decode`
+    pub name: String,
+    /// The directory its modules are in
+    pub dir: PathBuf,
+}
+
+impl SourceRoots {
+    /// The given directory, followed by the roots listed in its [SOURCE_ROOTS_FILENAME], if any.
+    pub fn from_dir<'a>(
+        source_provider: &dyn SourceProvider,
+        src_dir: &Path,
+    ) -> Result<Self, LoadingProblem<'a>> {
+        let filename = src_dir.join(SOURCE_ROOTS_FILENAME);
+        let mut roots = vec![src_dir.to_path_buf()];
+        let mut packages = Vec::new();
+
+        match source_provider.read(&filename) {
+            Ok(bytes) => {
+                let src = validate_utf8(&filename, &bytes)?;
+
+                for (index, line) in Self::lines(src) {
+                    match line.split_once(':') {
+                        Some((name, dir)) => {
+                            let (name, dir) = (name.trim(), dir.trim());
+
+                            if !is_package_name(name) || dir.is_empty() {
+                                return Err(LoadingProblem::FormattedReport(format!(
+                                    "{SOURCE_ROOTS_FILENAME}:{}: expected `package: directory`, got `{line}`",
+                                    index + 1
+                                )));
+                            }
+
+                            packages.push(SourcePackage {
+                                name: name.to_string(),
+                                dir: src_dir.join(dir),
+                            });
+                        }
+                        None => roots.push(src_dir.join(line)),
+                    }
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(LoadingProblem::FileProblem {
+                    filename,
+                    error: err.kind(),
+                })
+            }
+        }
+
+        Ok(Self {
+            roots,
+            packages,
+            listings: Default::default(),
+        })
+    }
+
+    /// The non-empty lines with their comments removed, along with their 0-based line numbers
+    fn lines(src: &str) -> impl Iterator<Item = (usize, &str)> {
+        src.lines().enumerate().filter_map(|(index, line)| {
+            let line = match line.split_once('#') {
+                Some((before_comment, _)) => before_comment,
+                None => line,
+            }
+            .trim();
+
+            (!line.is_empty()).then_some((index, line))
+        })
+    }
+
+    /// The root module's directory.
+    pub fn primary(&self) -> &Path {
+        &self.roots[0]
+    }
+
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    pub fn packages(&self) -> &[SourcePackage] {
+        &self.packages
+    }
+
+    /// Where the module with one of these relative paths lives. The paths are tried in order
+    /// (e.g. `Foo/Bar.wasm32.roc` before `Foo/Bar.roc`), and the first one found in any root wins.
+    ///
+    /// If no root has any of them, this is the last path under the primary root, so the caller
+    /// reports the usual file-not-found problem. If several roots have the winning path, all of
+    /// those are returned.
+    pub fn resolve(
+        &self,
+        source_provider: &dyn SourceProvider,
+        relative_paths: &[PathBuf],
+    ) -> Result<PathBuf, Vec<PathBuf>> {
+        for relative_path in relative_paths {
+            let mut found: Vec<PathBuf> = self
+                .roots
+                .iter()
+                .map(|root| root.join(relative_path))
+                .filter(|path| source_provider.exists(path))
+                .collect();
+
+            match found.len() {
+                0 => continue,
+                1 => return Ok(found.pop().unwrap()),
+                _ => return Err(found),
+            }
+        }
+
+        let default = relative_paths.last().expect("at least one path to resolve");
+
+        Ok(self.primary().join(default))
+    }
+
+    /// Every path [SourceRoots::resolve] looks at for these relative paths, in the order it
+    /// looks at them.
+    pub fn searched_paths(&self, relative_paths: &[PathBuf]) -> Vec<PathBuf> {
+        relative_paths
+            .iter()
+            .flat_map(|relative_path| self.roots.iter().map(|root| root.join(relative_path)))
+            .collect()
+    }
+
+    /// If there is a file at `path`, which is under `root`, but spelled with different casing,
+    /// the path as it is spelled in the source provider. A missing file is not a mismatch.
+    pub fn case_mismatch(
+        &self,
+        source_provider: &dyn SourceProvider,
+        root: &Path,
+        path: &Path,
+    ) -> Option<PathBuf> {
+        let relative_path = path.strip_prefix(root).ok()?;
+        let mut on_disk = root.to_path_buf();
+        let mut mismatch = false;
+
+        for component in relative_path.components() {
+            let expected = component.as_os_str();
+            let names = self.listing(source_provider, &on_disk)?;
+
+            if names.iter().any(|name| name == expected) {
+                on_disk.push(expected);
+            } else {
+                let expected = expected.to_str()?;
+                let found = names.iter().find(|name| {
+                    name.to_str()
+                        .map_or(false, |name| name.eq_ignore_ascii_case(expected))
+                })?;
+
+                on_disk.push(found);
+                mismatch = true;
+            }
+        }
+
+        mismatch.then_some(on_disk)
+    }
+
+    /// The names directly inside `dir`, listed through the provider the first time they're needed
+    fn listing(&self, source_provider: &dyn SourceProvider, dir: &Path) -> Option<Arc<[OsString]>> {
+        if let Some(names) = self.listings.lock().get(dir) {
+            return names.clone();
+        }
+
+        // not holding the lock while listing; another worker listing it too is harmless
+        let names: Option<Arc<[OsString]>> = source_provider
+            .list_dir(dir)
+            .ok()
+            .map(|entries| entries.into_iter().map(|entry| entry.name).collect());
+
+        self.listings
+            .lock()
+            .insert(dir.to_path_buf(), names.clone());
+
+        names
+    }
+}
+
+/// The `.roc` files under the roots whose paths are close to `relative_path`, closest first.
+/// This catches typos (`Utils/Parsr.roc` for `Utils/Parser.roc`) as well as files in the wrong
+/// directory (`Parser.roc` or `Util/Parser.roc` for `Utils/Parser.roc`).
+pub fn near_misses(
+    source_provider: &dyn SourceProvider,
+    roots: &[PathBuf],
+    relative_path: &Path,
+) -> Vec<PathBuf> {
+    let wanted = comparison_key(relative_path);
+    let wanted_stem = relative_path.file_stem();
+    let max_distance = (wanted.len() / 3).max(2);
+
+    let mut files = Vec::new();
+
+    for root in roots {
+        roc_files_under(source_provider, root, 0, &mut files);
+    }
+
+    let mut scored: Vec<(usize, PathBuf)> = files
+        .into_iter()
+        .filter_map(|path| {
+            let root = roots.iter().find(|root| path.starts_with(root))?;
+            let relative = path.strip_prefix(root).ok()?;
+            let distance = distance::damerau_levenshtein(&wanted, &comparison_key(relative));
+
+            let is_near = distance <= max_distance || relative.file_stem() == wanted_stem;
+
+            (is_near && distance > 0).then_some((distance, path))
+        })
+        .collect();
+
+    scored.sort();
+    scored.dedup_by(|(_, a), (_, b)| a == b);
+
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, path)| path)
+        .collect()
+}
+
+/// Package shorthands are lowercase, like `pf` or `json`
+fn is_package_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .map_or(false, |first| first.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+/// `Foo/Bar.roc` as `Foo/Bar`, with the same separator on every platform
+fn comparison_key(relative_path: &Path) -> String {
+    let without_extension = relative_path.with_extension("");
+    let parts: Vec<_> = without_extension
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+
+    parts.join("/")
+}
+
+fn roc_files_under(
+    source_provider: &dyn SourceProvider,
+    dir: &Path,
+    depth: usize,
+    files: &mut Vec<PathBuf>,
+) {
+    if depth > MAX_SEARCH_DEPTH {
+        return;
+    }
+
+    let Ok(entries) = source_provider.list_dir(dir) else {
+        return;
+    };
+
+    for entry in entries {
+        if files.len() >= MAX_FILES_SEARCHED {
+            return;
+        }
+
+        let path = dir.join(&entry.name);
+
+        if entry.is_dir {
+            // skip things like .git
+            if !entry.name.to_string_lossy().starts_with('.') {
+                roc_files_under(source_provider, &path, depth + 1, files);
+            }
+        } else if path.extension() == Some(OsStr::new("roc")) {
+            files.push(path);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+interface Main
+    exposes [parsed]
+    imports [Utils.Parser]
+
+parsed = Utils.Parser.parse "42"
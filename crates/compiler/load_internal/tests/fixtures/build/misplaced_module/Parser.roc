@@ -0,0 +1,5 @@
+interface Parser
+    exposes [parse]
+    imports []
+
+parse = \str -> str
@@ -0,0 +1,5 @@
+interface Util.Parser
+    exposes [parse]
+    imports []
+
+parse = \str -> str
@@ -0,0 +1,5 @@
+interface Main
+    exposes [answer]
+    imports [Answer]
+
+answer = Answer.value
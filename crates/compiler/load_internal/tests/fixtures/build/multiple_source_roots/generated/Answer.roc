@@ -0,0 +1,5 @@
+interface Answer
+    exposes [value]
+    imports []
+
+value = 42
@@ -0,0 +1,5 @@
+interface Main
+    exposes [answer]
+    imports [json.Answer]
+
+answer = Answer.value
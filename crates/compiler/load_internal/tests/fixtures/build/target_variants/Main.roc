@@ -0,0 +1,5 @@
+interface Main
+    exposes [pointerBits]
+    imports [Sys]
+
+pointerBits = Sys.pointerBits
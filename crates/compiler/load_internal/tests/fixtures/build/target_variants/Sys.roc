@@ -0,0 +1,5 @@
+interface Sys
+    exposes [pointerBits]
+    imports []
+
+pointerBits = "unknown"
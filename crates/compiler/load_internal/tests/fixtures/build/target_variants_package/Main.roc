@@ -0,0 +1,5 @@
+interface Main
+    exposes [pointerBits]
+    imports [sys.Sys]
+
+pointerBits = Sys.pointerBits
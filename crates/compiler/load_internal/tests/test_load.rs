@@ -18,14 +18,19 @@ use crate::helpers::fixtures_dir;
 use bumpalo::Bump;
 use roc_can::module::ExposedByModule;
 use roc_load_internal::file::{
-    ExecutionMode, LoadConfig, LoadResult, LoadStart, LoadingProblem, Threading,
+    ExecutionMode, LoadConfig, LoadResult, LoadStart, LoadingProblem, Threading, WorkerArenas,
+    DEFAULT_MAX_FILE_SIZE,
 };
+use roc_load_internal::layering::{LayerRules, LAYER_RULES_FILENAME};
 use roc_load_internal::module::LoadedModule;
+use roc_load_internal::progress::ProgressListener;
+use roc_load_internal::source_provider::{DirEntry, SourceProvider};
+use roc_load_internal::source_roots::{near_misses, SourceRoots};
 use roc_module::ident::ModuleName;
-use roc_module::symbol::{Interns, ModuleId};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_packaging::cache::RocCacheDir;
 use roc_problem::can::Problem;
-use roc_region::all::LineInfo;
+use roc_region::all::{LineInfo, Loc};
 use roc_reporting::report::RenderTarget;
 use roc_reporting::report::RocDocAllocator;
 use roc_reporting::report::{can_problem, DEFAULT_PALETTE};
@@ -33,8 +38,11 @@ use roc_solve::FunctionKind;
 use roc_target::TargetInfo;
 use roc_types::pretty_print::name_and_print_var;
 use roc_types::pretty_print::DebugPrint;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 fn load_and_typecheck(
     arena: &Bump,
@@ -42,6 +50,7 @@ fn load_and_typecheck(
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
     function_kind: FunctionKind,
+    layer_rules: LayerRules,
 ) -> Result<LoadedModule, LoadingProblem> {
     use LoadResult::*;
 
@@ -51,14 +60,13 @@ fn load_and_typecheck(
         RenderTarget::Generic,
         RocCacheDir::Disallowed,
         DEFAULT_PALETTE,
+        DEFAULT_MAX_FILE_SIZE,
     )?;
     let load_config = LoadConfig {
-        target_info,
-        function_kind,
         render: RenderTarget::Generic,
-        palette: DEFAULT_PALETTE,
         threading: Threading::Single,
-        exec_mode: ExecutionMode::Check,
+        layer_rules,
+        ..LoadConfig::new(target_info, function_kind, ExecutionMode::Check)
     };
 
     match roc_load_internal::file::load(
@@ -69,7 +77,7 @@ fn load_and_typecheck(
         RocCacheDir::Disallowed,
         load_config,
     )? {
-        Monomorphized(_) => unreachable!(""),
+        HeadersLoaded(_) | Monomorphized(_) => unreachable!(""),
         TypeChecked(module) => Ok(module),
     }
 }
@@ -105,10 +113,18 @@ fn format_can_problems(
 }
 
 fn multiple_modules(subdir: &str, files: Vec<(&str, &str)>) -> Result<LoadedModule, String> {
+    multiple_modules_with_layer_rules(subdir, files, LayerRules::default())
+}
+
+fn multiple_modules_with_layer_rules(
+    subdir: &str,
+    files: Vec<(&str, &str)>,
+    layer_rules: LayerRules,
+) -> Result<LoadedModule, String> {
     let arena = Bump::new();
     let arena = &arena;
 
-    match multiple_modules_help(subdir, arena, files) {
+    match multiple_modules_help(subdir, arena, files, layer_rules) {
         Err(io_error) => panic!("IO trouble: {io_error:?}"),
         Ok(Err(LoadingProblem::FormattedReport(buf))) => Err(buf),
         Ok(Err(loading_problem)) => Err(format!("{loading_problem:?}")),
@@ -142,6 +158,7 @@ fn multiple_modules_help<'a>(
     subdir: &str,
     arena: &'a Bump,
     mut files: Vec<(&str, &str)>,
+    layer_rules: LayerRules,
 ) -> Result<Result<LoadedModule, roc_load_internal::file::LoadingProblem<'a>>, std::io::Error> {
     use std::fs::{self, File};
     use std::io::Write;
@@ -185,6 +202,7 @@ fn multiple_modules_help<'a>(
             Default::default(),
             TARGET_INFO,
             FunctionKind::LambdaSet,
+            layer_rules,
         )
     };
 
@@ -205,6 +223,7 @@ fn load_fixture(
         subs_by_module,
         TARGET_INFO,
         FunctionKind::LambdaSet,
+        LayerRules::default(),
     );
     let mut loaded_module = match loaded {
         Ok(x) => x,
@@ -368,6 +387,7 @@ fn interface_with_deps() {
         subs_by_module,
         TARGET_INFO,
         FunctionKind::LambdaSet,
+        LayerRules::default(),
     );
 
     let mut loaded_module = loaded.expect("Test module failed to load");
@@ -662,6 +682,50 @@ fn parse_problem() {
     }
 }
 
+#[test]
+fn parse_problem_with_recovery() {
+    let arena = Bump::new();
+    let src = indoc!(
+        r#"
+        interface Main exposes [main, other] imports []
+
+        main = [
+
+        other = "other"
+        "#
+    );
+
+    let load_start = LoadStart::from_str(
+        &arena,
+        PathBuf::from("Main.roc"),
+        src,
+        RocCacheDir::Disallowed,
+        PathBuf::from("."),
+    )
+    .unwrap();
+    let load_config = LoadConfig {
+        recover_from_syntax_errors: true,
+        ..load_config_in_mode(ExecutionMode::Check)
+    };
+
+    let mut loaded = match load_from_start(&arena, load_start, load_config) {
+        Ok(LoadResult::TypeChecked(loaded)) => loaded,
+        Ok(_) => unreachable!(""),
+        Err(problem) => panic!("the syntax error should not stop the load: {problem:?}"),
+    };
+    let problems = loaded.can_problems.remove(&loaded.module_id).unwrap();
+
+    // `other` still got checked, so only `main` is missing
+    match problems.as_slice() {
+        [Problem::SyntaxError { region, .. }, Problem::ExposedButNotDefined(symbol, _)] => {
+            assert_eq!(region.start().offset as usize, src.find("main =").unwrap());
+            assert_eq!(region.end().offset as usize, src.find("other =").unwrap());
+            assert_eq!(symbol.as_str(&loaded.interns), "main");
+        }
+        other => panic!("expected a syntax error in `main`, but got {other:?}"),
+    }
+}
+
 #[test]
 #[should_panic(expected = "FILE NOT FOUND")]
 fn file_not_found() {
@@ -677,7 +741,7 @@ fn file_not_found() {
 }
 
 #[test]
-#[should_panic(expected = "FILE NOT FOUND")]
+#[should_panic(expected = "MODULE NOT FOUND")]
 fn imported_file_not_found() {
     let subs_by_module = Default::default();
     let loaded_module = load_fixture("no_deps", "MissingDep", subs_by_module);
@@ -951,6 +1015,179 @@ fn issue_2863_module_type_does_not_exist() {
     }
 }
 
+#[test]
+fn unknown_name_exposed_by_module_not_imported() {
+    let modules = vec![
+        (
+            "Helpers",
+            indoc!(
+                r#"
+                    interface Helpers exposes [double] imports []
+
+                    double = \n -> n * 2
+                    "#
+            ),
+        ),
+        (
+            "Middle",
+            indoc!(
+                r#"
+                    interface Middle exposes [quadruple] imports [Helpers]
+
+                    quadruple = \n -> Helpers.double (Helpers.double n)
+                    "#
+            ),
+        ),
+        (
+            "Main.roc",
+            indoc!(
+                r#"
+                    interface Main exposes [main] imports [Middle]
+
+                    main = Middle.quadruple (double 1)
+                    "#
+            ),
+        ),
+    ];
+
+    let report = multiple_modules("unknown_name_exposed_by_module_not_imported", modules)
+        .expect_err("`double` is not in scope in Main");
+    let report = report.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    assert!(
+        report.contains(
+            "Tip: Helpers exposes a value with this name. Import Helpers to refer to it as `Helpers.double`."
+        ),
+        "{report}"
+    );
+}
+
+#[test]
+fn unknown_name_exposed_with_non_ascii_first_letter() {
+    let modules = vec![
+        (
+            "Helpers",
+            indoc!(
+                r#"
+                    interface Helpers exposes [écho] imports []
+
+                    écho = \s -> s
+                    "#
+            ),
+        ),
+        (
+            "Middle",
+            indoc!(
+                r#"
+                    interface Middle exposes [twice] imports [Helpers]
+
+                    twice = \s -> Helpers.écho (Helpers.écho s)
+                    "#
+            ),
+        ),
+        (
+            "Main.roc",
+            indoc!(
+                r#"
+                    interface Main exposes [main] imports [Middle]
+
+                    main = Middle.twice (écho "hi")
+                    "#
+            ),
+        ),
+    ];
+
+    let report = multiple_modules("unknown_name_exposed_with_non_ascii_first_letter", modules)
+        .expect_err("`écho` is not in scope in Main");
+    let report = report.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    assert!(
+        report.contains(
+            "Tip: Helpers exposes a value with this name. Import Helpers to refer to it as `Helpers.écho`."
+        ),
+        "{report}"
+    );
+}
+
+#[test]
+fn unknown_name_suggestions_do_not_depend_on_load_order() {
+    // Only B imports Helpers, so whether A's suggestions could see Helpers' header would
+    // otherwise depend on which thread got there first
+    let dir = PathBuf::from("/not/on/disk");
+    let sources = hashmap! {
+        dir.join("Main.roc") => indoc!(
+            r#"
+            interface Main exposes [main] imports [A, B]
+
+            main = A.a + B.b
+            "#
+        ),
+        dir.join("A.roc") => indoc!(
+            r#"
+            interface A exposes [a] imports []
+
+            a = double 1
+            "#
+        ),
+        dir.join("B.roc") => indoc!(
+            r#"
+            interface B exposes [b] imports [Helpers]
+
+            b = Helpers.double 2
+            "#
+        ),
+        dir.join("Helpers.roc") => indoc!(
+            r#"
+            interface Helpers exposes [double] imports []
+
+            double = \n -> n * 2
+            "#
+        ),
+    };
+
+    let report_for_a = || {
+        let arena = Bump::new();
+        let load_start = LoadStart::from_path_with_provider(
+            &arena,
+            dir.join("Main.roc"),
+            RenderTarget::Generic,
+            RocCacheDir::Disallowed,
+            DEFAULT_PALETTE,
+            DEFAULT_MAX_FILE_SIZE,
+            Arc::new(InMemorySources(sources.clone())),
+        )
+        .unwrap();
+        let load_config = LoadConfig {
+            threading: Threading::AtMost(4),
+            ..load_config_in_mode(ExecutionMode::Check)
+        };
+
+        let mut loaded = match load_from_start(&arena, load_start, load_config).unwrap() {
+            LoadResult::TypeChecked(loaded) => loaded,
+            LoadResult::HeadersLoaded(_) | LoadResult::Monomorphized(_) => unreachable!(""),
+        };
+
+        let a = loaded
+            .interns
+            .module_ids
+            .get_id(&ModuleName::from("A"))
+            .unwrap();
+        let (filename, src) = loaded.sources.get(&a).unwrap().clone();
+        let problems = loaded.can_problems.remove(&a).unwrap_or_default();
+
+        format_can_problems(problems, a, &loaded.interns, filename, &src)
+    };
+
+    let first = report_for_a();
+
+    assert!(first.contains("double"), "{first}");
+    assert!(!first.contains("Helpers"), "{first}");
+
+    for _ in 0..20 {
+        assert_eq!(report_for_a(), first);
+    }
+}
+
 #[test]
 fn import_builtin_in_platform_and_check_app() {
     let modules = vec![
@@ -1014,6 +1251,9 @@ fn module_doesnt_match_file_path() {
             1│  interface NotAge exposes [Age] imports []
                           ^^^^^^
 
+            Based on its name, I expect NotAge to be defined in NotAge.roc,
+            relative to the source directory.
+
             Module names must correspond with the file paths they are defined in.
             For example, I expect to see BigNum defined in BigNum.roc, or Math.Sin
             defined in Math/Sin.roc."#
@@ -1148,3 +1388,1360 @@ fn nested_module_has_incorrect_name() {
         err
     );
 }
+
+#[test]
+fn forbidden_layer_import() {
+    let modules = vec![
+        (
+            "Db",
+            indoc!(
+                r#"
+                interface Db exposes [query] imports []
+
+                query = 1
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                interface Main exposes [main] imports [Db]
+
+                main = Db.query
+                "#
+            ),
+        ),
+    ];
+
+    let layer_rules = LayerRules::parse("Main may not import Db.*").unwrap();
+    let err = multiple_modules_with_layer_rules("forbidden_layer_import", modules, layer_rules)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        indoc!(
+            r#"
+            ── FORBIDDEN IMPORT ────────────────────────── tmp/forbidden_layer_import/Main ─
+
+            This module is not allowed to import Db:
+
+            1│  interface Main exposes [main] imports [Db]
+                                                       ^^
+
+            Your project's layering rules forbid this import:
+
+                Main may not import Db.*
+
+            Can you get what you need from an allowed module instead?
+            "#
+        ),
+        "\n{}",
+        err
+    );
+}
+
+#[test]
+fn forbidden_layer_import_in_app() {
+    let modules = vec![
+        (
+            "platform/main.roc",
+            indoc!(
+                r#"
+                platform "testplatform"
+                    requires {} { main : Str }
+                    exposes []
+                    packages {}
+                    imports []
+                    provides [mainForHost]
+
+                mainForHost : Str
+                mainForHost = main
+                "#
+            ),
+        ),
+        (
+            "Db",
+            indoc!(
+                r#"
+                interface Db exposes [query] imports []
+
+                query = "query"
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                app "test"
+                    packages { pf: "platform/main.roc" }
+                    imports [Db]
+                    provides [main] to pf
+
+                main = Db.query
+                "#
+            ),
+        ),
+    ];
+
+    // apps have no module name, so only rules for every importer cover them
+    let layer_rules = LayerRules::parse(indoc!(
+        r#"
+        Main may not import Db
+        * may not import Db.*
+        "#
+    ))
+    .unwrap();
+    let err =
+        multiple_modules_with_layer_rules("forbidden_layer_import_in_app", modules, layer_rules)
+            .unwrap_err();
+
+    assert!(err.contains("FORBIDDEN IMPORT"), "{err}");
+    assert!(err.contains("* may not import Db.*"), "{err}");
+    assert!(!err.contains("Main may not import Db"), "{err}");
+}
+
+#[test]
+fn layer_rules_for_package_modules() {
+    let layer_rules = LayerRules::parse(indoc!(
+        r#"
+        Ui.* may not import pf.*
+        Db may not import json.Decode
+        "#
+    ))
+    .unwrap();
+
+    assert!(layer_rules.forbidding("Ui.Button", "pf.Stdout").is_some());
+    assert!(layer_rules.forbidding("Ui.Button", "json.Decode").is_none());
+    assert!(layer_rules.forbidding("Db", "json.Decode").is_some());
+    assert!(layer_rules.forbidding("Db", "json.Encode").is_none());
+
+    assert!(LayerRules::parse("Ui may not import Pf.stdout").is_err());
+}
+
+#[test]
+fn import_chain_of_transitive_dependency() {
+    let modules = vec![
+        (
+            "Parser",
+            indoc!(
+                r#"
+                interface Parser exposes [null] imports []
+
+                null = 0
+                "#
+            ),
+        ),
+        (
+            "Http",
+            indoc!(
+                r#"
+                interface Http exposes [get] imports [Parser]
+
+                get = Parser.null
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                interface Main exposes [main] imports [Http]
+
+                main = Http.get
+                "#
+            ),
+        ),
+    ];
+
+    let loaded = multiple_modules("import_chain_of_transitive_dependency", modules).unwrap();
+
+    let module_id = |name: &str| {
+        loaded
+            .interns
+            .module_ids
+            .get_id(&ModuleName::from(name))
+            .unwrap()
+    };
+
+    assert_eq!(
+        loaded.import_chain(module_id("Parser")),
+        vec![module_id("Http"), module_id("Main")]
+    );
+    assert_eq!(
+        loaded.import_chain(module_id("Http")),
+        vec![module_id("Main")]
+    );
+    assert_eq!(loaded.import_chain(loaded.module_id), vec![]);
+}
+
+#[test]
+fn import_chain_does_not_depend_on_load_order() {
+    let dir = PathBuf::from("/not/on/disk");
+    let sources = hashmap! {
+        dir.join("Main.roc") => "interface Main exposes [x] imports [Long, Short, A, Z]\n\nx = 1\n",
+        dir.join("Long.roc") => "interface Long exposes [x] imports [Mid]\n\nx = 1\n",
+        dir.join("Mid.roc") => "interface Mid exposes [x] imports [Target]\n\nx = 1\n",
+        dir.join("Short.roc") => "interface Short exposes [x] imports [Target]\n\nx = 1\n",
+        dir.join("Target.roc") => "interface Target exposes [x] imports []\n\nx = 1\n",
+        dir.join("A.roc") => "interface A exposes [x] imports [Shared]\n\nx = 1\n",
+        dir.join("Z.roc") => "interface Z exposes [x] imports [Shared]\n\nx = 1\n",
+        dir.join("Shared.roc") => "interface Shared exposes [x] imports []\n\nx = 1\n",
+    };
+
+    for _ in 0..20 {
+        let arena = Bump::new();
+        let load_start = LoadStart::from_path_with_provider(
+            &arena,
+            dir.join("Main.roc"),
+            RenderTarget::Generic,
+            RocCacheDir::Disallowed,
+            DEFAULT_PALETTE,
+            DEFAULT_MAX_FILE_SIZE,
+            Arc::new(InMemorySources(sources.clone())),
+        )
+        .unwrap();
+        let load_config = LoadConfig {
+            threading: Threading::AtMost(4),
+            ..load_config_in_mode(ExecutionMode::Check)
+        };
+
+        let loaded = match load_from_start(&arena, load_start, load_config).unwrap() {
+            LoadResult::TypeChecked(loaded) => loaded,
+            LoadResult::HeadersLoaded(_) | LoadResult::Monomorphized(_) => unreachable!(""),
+        };
+
+        let chain = |name: &str| -> Vec<&str> {
+            let module_id = loaded
+                .interns
+                .module_ids
+                .get_id(&ModuleName::from(name))
+                .unwrap();
+
+            loaded
+                .import_chain(module_id)
+                .into_iter()
+                .map(|module_id| loaded.interns.module_name(module_id).as_str())
+                .collect()
+        };
+
+        // the shorter of the two chains
+        assert_eq!(chain("Target"), vec!["Short", "Main"]);
+        // two chains of the same length; the importer with the smaller path wins
+        assert_eq!(chain("Shared"), vec!["A", "Main"]);
+    }
+}
+
+#[test]
+fn module_graph_of_loaded_modules() {
+    let modules = vec![
+        (
+            "Parser",
+            indoc!(
+                r#"
+                interface Parser exposes [null, parse] imports []
+
+                null = 0
+
+                parse = \_ -> null
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                interface Main exposes [main] imports [Parser]
+
+                main = Parser.null
+                "#
+            ),
+        ),
+    ];
+
+    let loaded = multiple_modules("module_graph_of_loaded_modules", modules).unwrap();
+    let parser = loaded
+        .interns
+        .module_ids
+        .get_id(&ModuleName::from("Parser"))
+        .unwrap();
+
+    let node = &loaded.module_graph.modules[&parser];
+    let exposed: Vec<&str> = node
+        .exposed
+        .iter()
+        .map(|symbol| symbol.as_str(&loaded.interns))
+        .collect();
+
+    assert_eq!(exposed, vec!["null", "parse"]);
+    assert!(node.path.ends_with("Parser.roc"));
+    assert!(loaded.module_graph.modules[&loaded.module_id]
+        .imports
+        .contains(&parser));
+    assert_eq!(
+        loaded.module_graph.dependents(parser).collect::<Vec<_>>(),
+        vec![loaded.module_id]
+    );
+}
+
+/// Load a fixture in the given mode, with the same settings as [load_and_typecheck]
+fn load_in_mode<'a>(
+    arena: &'a Bump,
+    dir_name: &str,
+    module_name: &str,
+    exec_mode: ExecutionMode,
+    progress: Option<Arc<dyn ProgressListener>>,
+) -> LoadResult<'a> {
+    let filename = fixtures_dir()
+        .join(dir_name)
+        .join(format!("{module_name}.roc"));
+    let load_start = LoadStart::from_path(
+        arena,
+        filename,
+        RenderTarget::Generic,
+        RocCacheDir::Disallowed,
+        DEFAULT_PALETTE,
+        DEFAULT_MAX_FILE_SIZE,
+    )
+    .unwrap();
+    let load_config = LoadConfig {
+        progress,
+        ..load_config_in_mode(exec_mode)
+    };
+
+    load_from_start(arena, load_start, load_config).unwrap()
+}
+
+/// The settings [load_and_typecheck] uses, in the given mode
+fn load_config_in_mode(exec_mode: ExecutionMode) -> LoadConfig {
+    LoadConfig {
+        render: RenderTarget::Generic,
+        threading: Threading::Single,
+        ..LoadConfig::new(TARGET_INFO, FunctionKind::LambdaSet, exec_mode)
+    }
+}
+
+/// Finish a load that is already started, compiling the builtins from scratch
+fn load_from_start<'a>(
+    arena: &'a Bump,
+    load_start: LoadStart<'a>,
+    load_config: LoadConfig,
+) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
+    roc_load_internal::file::load(
+        arena,
+        load_start,
+        Default::default(),
+        Default::default(),
+        RocCacheDir::Disallowed,
+        load_config,
+    )
+}
+
+#[test]
+fn load_headers_only() {
+    use LoadResult::*;
+
+    let arena = Bump::new();
+    let headers = match load_in_mode(
+        &arena,
+        "interface_with_deps",
+        "Primary",
+        ExecutionMode::HeadersOnly,
+        None,
+    ) {
+        HeadersLoaded(headers) => headers,
+        TypeChecked(_) | Monomorphized(_) => unreachable!(""),
+    };
+
+    let module_id = |name: &str| {
+        headers
+            .interns
+            .module_ids
+            .get_id(&ModuleName::from(name))
+            .unwrap()
+    };
+    let blah = module_id("Dep3.Blah");
+    let dep2 = module_id("Dep2");
+
+    assert!(headers.module_graph.modules[&blah]
+        .path
+        .ends_with("Dep3/Blah.roc"));
+    assert_eq!(
+        headers
+            .module_graph
+            .dependents(blah)
+            .collect::<HashSet<_>>(),
+        hashset! { module_id("Dep1"), dep2, headers.root_id }
+    );
+
+    let exposed: Vec<&str> = headers.module_graph.modules[&dep2]
+        .exposed
+        .iter()
+        .map(|symbol| symbol.as_str(&headers.interns))
+        .collect();
+
+    assert_eq!(exposed, vec!["one", "two", "blah"]);
+}
+
+#[test]
+fn check_problems_only_drops_declarations() {
+    use LoadResult::*;
+
+    let arena = Bump::new();
+    let loaded = match load_in_mode(
+        &arena,
+        "interface_with_deps",
+        "Primary",
+        ExecutionMode::CheckProblemsOnly,
+        None,
+    ) {
+        TypeChecked(loaded) => loaded,
+        HeadersLoaded(_) | Monomorphized(_) => unreachable!(""),
+    };
+
+    assert_eq!(loaded.total_problems(), 0);
+    assert!(loaded.declarations_by_id.is_empty());
+    assert!(loaded.typechecked.is_empty());
+    assert!(loaded.timings.contains_key(&loaded.module_id));
+}
+
+#[derive(Default)]
+struct RecordProgress {
+    events: Mutex<Vec<(&'static str, ModuleId)>>,
+}
+
+impl ProgressListener for RecordProgress {
+    fn module_discovered(&self, module_id: ModuleId, _path: &Path) {
+        self.events.lock().unwrap().push(("discovered", module_id));
+    }
+
+    fn module_parsed(&self, module_id: ModuleId) {
+        self.events.lock().unwrap().push(("parsed", module_id));
+    }
+
+    fn module_solved(&self, module_id: ModuleId) {
+        self.events.lock().unwrap().push(("solved", module_id));
+    }
+}
+
+#[test]
+fn progress_listener_sees_every_module() {
+    use LoadResult::*;
+
+    let arena = Bump::new();
+    let progress = Arc::new(RecordProgress::default());
+    let loaded = match load_in_mode(
+        &arena,
+        "interface_with_deps",
+        "Primary",
+        ExecutionMode::Check,
+        Some(progress.clone()),
+    ) {
+        TypeChecked(loaded) => loaded,
+        HeadersLoaded(_) | Monomorphized(_) => unreachable!(""),
+    };
+
+    let events = progress.events.lock().unwrap();
+    let steps_of = |module_id: ModuleId| {
+        events
+            .iter()
+            .filter(|(_, id)| *id == module_id)
+            .map(|(step, _)| *step)
+            .collect::<Vec<_>>()
+    };
+
+    for module_id in loaded.sources.keys() {
+        assert_eq!(
+            steps_of(*module_id),
+            vec!["discovered", "parsed", "solved"],
+            "{module_id:?}"
+        );
+    }
+}
+
+#[test]
+fn load_more_modules_than_fit_in_a_channel() {
+    // Main imports all of these at once, so the coordinator starts more tasks in one go
+    // than there used to be room for in the (then bounded) channels.
+    const MODULE_COUNT: usize = 1500;
+
+    let dir = roc_test_utils::TmpDir::new("tmp/load_more_modules_than_fit_in_a_channel");
+
+    let names: Vec<String> = (0..MODULE_COUNT).map(|i| format!("Dep{i}")).collect();
+
+    for name in names.iter() {
+        let src = format!("interface {name} exposes [value] imports []\n\nvalue = 1\n");
+
+        std::fs::write(dir.path().join(format!("{name}.roc")), src).unwrap();
+    }
+
+    let main_src = format!(
+        "interface Main exposes [main] imports [{}]\n\nmain = Dep0.value\n",
+        names.join(", ")
+    );
+    let main_path = dir.path().join("Main.roc");
+    std::fs::write(&main_path, main_src).unwrap();
+
+    let arena = Bump::new();
+    let loaded = load_and_typecheck(
+        &arena,
+        main_path,
+        Default::default(),
+        TARGET_INFO,
+        FunctionKind::LambdaSet,
+        LayerRules::default(),
+    )
+    .unwrap();
+
+    let loaded_from_dir = loaded
+        .sources
+        .values()
+        .filter(|(path, _)| path.starts_with(dir.path()))
+        .count();
+
+    assert_eq!(loaded_from_dir, MODULE_COUNT + 1);
+}
+
+#[test]
+fn reuse_worker_arenas_across_loads() {
+    let filename = fixtures_dir()
+        .join("interface_with_deps")
+        .join("Primary.roc");
+    let mut worker_arenas = WorkerArenas::default();
+
+    for _ in 0..2 {
+        worker_arenas.reset();
+
+        let arena = Bump::new();
+        let load_start = LoadStart::from_path(
+            &arena,
+            filename.clone(),
+            RenderTarget::Generic,
+            RocCacheDir::Disallowed,
+            DEFAULT_PALETTE,
+            DEFAULT_MAX_FILE_SIZE,
+        )
+        .unwrap()
+        .with_worker_arenas(&mut worker_arenas);
+        let load_config = LoadConfig {
+            threading: Threading::AtMost(3),
+            ..load_config_in_mode(ExecutionMode::Check)
+        };
+
+        let loaded = match load_from_start(&arena, load_start, load_config).unwrap() {
+            LoadResult::TypeChecked(loaded) => loaded,
+            LoadResult::HeadersLoaded(_) | LoadResult::Monomorphized(_) => unreachable!(""),
+        };
+
+        assert_eq!(loaded.total_problems(), 0);
+    }
+}
+
+/// Sources that only exist in memory.
+struct InMemorySources(HashMap<PathBuf, &'static str>);
+
+impl SourceProvider for InMemorySources {
+    fn read(&self, path: &Path) -> std::io::Result<Cow<'_, [u8]>> {
+        match self.0.get(path) {
+            Some(src) => Ok(Cow::Borrowed(src.as_bytes())),
+            None => Err(std::io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.0.contains_key(path)
+    }
+
+    fn modified_time(&self, _path: &Path) -> std::io::Result<SystemTime> {
+        Ok(SystemTime::UNIX_EPOCH)
+    }
+
+    fn list_dir(&self, dir: &Path) -> std::io::Result<Vec<DirEntry>> {
+        let mut entries: Vec<DirEntry> = Vec::new();
+
+        for path in self.0.keys() {
+            let Ok(relative_path) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let mut components = relative_path.components();
+
+            if let Some(first) = components.next() {
+                let entry = DirEntry {
+                    name: first.as_os_str().to_os_string(),
+                    is_dir: components.next().is_some(),
+                };
+
+                if !entries.contains(&entry) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Type-check the module at `filename`, reading everything through `sources`.
+/// A problem comes back as its report.
+fn load_through_provider(
+    filename: PathBuf,
+    sources: impl SourceProvider + 'static,
+    max_file_size: usize,
+) -> Result<LoadedModule, String> {
+    let arena = Bump::new();
+    let report = |problem: LoadingProblem| match problem {
+        LoadingProblem::FormattedReport(report) => report,
+        other => panic!("expected a report, but got {other:?}"),
+    };
+
+    let load_start = LoadStart::from_path_with_provider(
+        &arena,
+        filename,
+        RenderTarget::Generic,
+        RocCacheDir::Disallowed,
+        DEFAULT_PALETTE,
+        max_file_size,
+        Arc::new(sources),
+    )
+    .map_err(report)?;
+    let load_config = LoadConfig {
+        max_file_size,
+        ..load_config_in_mode(ExecutionMode::Check)
+    };
+
+    match load_from_start(&arena, load_start, load_config).map_err(report)? {
+        LoadResult::TypeChecked(loaded) => Ok(loaded),
+        LoadResult::HeadersLoaded(_) | LoadResult::Monomorphized(_) => unreachable!(""),
+    }
+}
+
+#[test]
+fn load_through_source_provider() {
+    let dir = PathBuf::from("/not/on/disk");
+    let sources = InMemorySources(hashmap! {
+        dir.join("Main.roc") => indoc!(
+            r#"
+            interface Main exposes [main] imports [Dep.Greeting]
+
+            main = Dep.Greeting.greeting
+            "#
+        ),
+        dir.join("Dep").join("Greeting.roc") => indoc!(
+            r#"
+            interface Dep.Greeting exposes [greeting] imports []
+
+            greeting = "hello"
+            "#
+        ),
+    });
+
+    let loaded =
+        load_through_provider(dir.join("Main.roc"), sources, DEFAULT_MAX_FILE_SIZE).unwrap();
+
+    assert_eq!(loaded.total_problems(), 0);
+    expect_types(
+        loaded,
+        hashmap! {
+            "main" => "Str",
+        },
+    );
+}
+
+#[test]
+fn byte_order_mark_is_ignored() {
+    let dir = PathBuf::from("/not/on/disk");
+    let sources = InMemorySources(hashmap! {
+        dir.join("Main.roc") => "\u{FEFF}interface Main exposes [main] imports [Dep]\n\nmain = Dep.dep\n",
+        dir.join("Dep.roc") => "\u{FEFF}interface Dep exposes [dep] imports []\n\ndep = 1u8\n",
+    });
+
+    let loaded =
+        load_through_provider(dir.join("Main.roc"), sources, DEFAULT_MAX_FILE_SIZE).unwrap();
+
+    assert_eq!(loaded.total_problems(), 0);
+    expect_types(
+        loaded,
+        hashmap! {
+            "main" => "U8",
+        },
+    );
+}
+
+#[test]
+fn imported_file_over_the_size_limit() {
+    let dir = PathBuf::from("/not/on/disk");
+    let sources = InMemorySources(hashmap! {
+        dir.join("Main.roc") => indoc!(
+            r#"
+            interface Main exposes [main] imports [Big]
+
+            main = Big.big
+            "#
+        ),
+        dir.join("Big.roc") => indoc!(
+            r#"
+            interface Big exposes [big] imports []
+
+            big = "a string that takes this module over the limit"
+            "#
+        ),
+    });
+
+    let report = load_through_provider(dir.join("Main.roc"), sources, 64).unwrap_err();
+
+    assert!(report.contains("FILE TOO LARGE"), "{report}");
+    assert!(report.contains("/not/on/disk/Big.roc"), "{report}");
+    assert!(
+        report.contains("It is 95 bytes, but I can only load files of up to 64 bytes."),
+        "{report}"
+    );
+}
+
+#[test]
+fn aliases_by_module() {
+    let modules = vec![
+        (
+            "Geometry",
+            indoc!(
+                r#"
+                interface Geometry exposes [Point, origin] imports []
+
+                Point : Pair F64
+
+                Pair a : (a, a)
+
+                origin : Point
+                origin = (0, 0)
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                interface Main exposes [main] imports [Geometry]
+
+                main = Geometry.origin
+                "#
+            ),
+        ),
+    ];
+
+    let loaded = multiple_modules("aliases_by_module", modules).unwrap();
+    let geometry = loaded
+        .interns
+        .module_ids
+        .get_id(&ModuleName::from("Geometry"))
+        .unwrap();
+
+    let mut aliases: Vec<(&str, usize)> = loaded.aliases_by_module[&geometry]
+        .iter()
+        .map(|(symbol, alias)| (symbol.as_str(&loaded.interns), alias.type_variables.len()))
+        .collect();
+    aliases.sort_unstable();
+
+    assert_eq!(aliases, vec![("Pair", 1), ("Point", 0)]);
+    assert!(loaded.aliases_by_module[&loaded.module_id].is_empty());
+}
+
+#[test]
+fn import_from_extra_source_root() {
+    let subs_by_module = Default::default();
+    let loaded_module = load_fixture("multiple_source_roots", "Main", subs_by_module);
+
+    expect_types(
+        loaded_module,
+        hashmap! {
+            "answer" => "Num *",
+        },
+    );
+}
+
+#[test]
+fn import_from_source_root_package() {
+    let subs_by_module = Default::default();
+    let loaded_module = load_fixture("source_root_packages", "Main", subs_by_module);
+
+    expect_types(
+        loaded_module,
+        hashmap! {
+            "answer" => "Num *",
+        },
+    );
+}
+
+#[test]
+fn import_target_variant_from_package() {
+    let subs_by_module = Default::default();
+    let loaded_module = load_fixture("target_variants_package", "Main", subs_by_module);
+
+    // sys.Sys resolves to vendor/sys/Sys.x86_64.roc, not vendor/sys/Sys.roc
+    expect_types(
+        loaded_module,
+        hashmap! {
+            "pointerBits" => "Num *",
+        },
+    );
+}
+
+#[test]
+fn import_target_variant() {
+    let subs_by_module = Default::default();
+    let loaded_module = load_fixture("target_variants", "Main", subs_by_module);
+
+    // TARGET_INFO is x86_64, so Sys.x86_64.roc is loaded instead of Sys.roc
+    expect_types(
+        loaded_module,
+        hashmap! {
+            "pointerBits" => "Num *",
+        },
+    );
+}
+
+#[test]
+fn module_in_two_source_roots() {
+    let arena = Bump::new();
+    let src_dir = fixtures_dir().join("ambiguous_source_roots");
+
+    let err = load_and_typecheck(
+        &arena,
+        src_dir.join("Main.roc"),
+        Default::default(),
+        TARGET_INFO,
+        FunctionKind::LambdaSet,
+        LayerRules::default(),
+    )
+    .unwrap_err();
+
+    match err {
+        LoadingProblem::FormattedReport(report) => {
+            assert!(report.contains("AMBIGUOUS MODULE"), "{report}");
+            assert!(
+                report.contains(src_dir.join("Answer.roc").to_str().unwrap()),
+                "{report}"
+            );
+            assert!(
+                report.contains(src_dir.join("generated/Answer.roc").to_str().unwrap()),
+                "{report}"
+            );
+        }
+        other => panic!("expected an ambiguous module report, but got {other:?}"),
+    }
+}
+
+#[test]
+fn imported_module_not_found_suggests_near_misses() {
+    let arena = Bump::new();
+    let src_dir = fixtures_dir().join("misplaced_module");
+
+    let err = load_and_typecheck(
+        &arena,
+        src_dir.join("Main.roc"),
+        Default::default(),
+        TARGET_INFO,
+        FunctionKind::LambdaSet,
+        LayerRules::default(),
+    )
+    .unwrap_err();
+
+    match err {
+        LoadingProblem::FormattedReport(report) => {
+            let position = |path: PathBuf| {
+                report
+                    .find(path.to_str().unwrap())
+                    .unwrap_or_else(|| panic!("{} is not in the report:\n{report}", path.display()))
+            };
+
+            assert!(report.contains("MODULE NOT FOUND"), "{report}");
+            position(src_dir.join("Utils").join("Parser.roc"));
+
+            // the typo is closer than the file in the wrong directory
+            assert!(
+                position(src_dir.join("Util").join("Parser.roc"))
+                    < position(src_dir.join("Parser.roc")),
+                "{report}"
+            );
+        }
+        other => panic!("expected a module not found report, but got {other:?}"),
+    }
+}
+
+#[test]
+fn near_misses_through_source_provider() {
+    let dir = PathBuf::from("/not/on/disk");
+    let sources = InMemorySources(hashmap! {
+        dir.join("Main.roc") => "interface Main exposes [] imports [Utils.Parser]\n",
+        dir.join("Util").join("Parser.roc") => "interface Util.Parser exposes [] imports []\n",
+        dir.join("Other.roc") => "interface Other exposes [] imports []\n",
+    });
+
+    let suggestions = near_misses(&sources, &[dir.clone()], Path::new("Utils/Parser.roc"));
+
+    assert_eq!(suggestions, vec![dir.join("Util").join("Parser.roc")]);
+}
+
+#[test]
+fn import_app_module() {
+    let modules = vec![
+        (
+            "Helper",
+            indoc!(
+                r#"
+                app "helper"
+                    packages { pf: "platform/main.roc" }
+                    provides [main] to pf
+
+                main = 1
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                interface Main exposes [main] imports [Helper]
+
+                main = Helper.main
+                "#
+            ),
+        ),
+    ];
+
+    let report = multiple_modules("import_app_module", modules).unwrap_err();
+
+    assert!(report.contains("IMPORTED APP MODULE"), "{report}");
+    assert!(report.contains("import_app_module/Helper.roc"), "{report}");
+
+    // the importer is listed with its kind of header
+    let importer = report
+        .lines()
+        .find(|line| line.contains("import_app_module/Main"))
+        .unwrap_or_else(|| panic!("the importer is not in the report:\n{report}"));
+    assert!(importer.trim_start().starts_with("interface "), "{report}");
+}
+
+#[test]
+fn layer_rules_through_source_provider() {
+    let dir = Path::new("/not/on/disk");
+    let sources = InMemorySources(hashmap! {
+        dir.join(LAYER_RULES_FILENAME) => "Ui.* may not import Db.*\n",
+    });
+
+    let layer_rules = LayerRules::for_root_module(&sources, &dir.join("Main.roc")).unwrap();
+
+    assert_eq!(layer_rules.rules().len(), 1);
+    assert!(layer_rules.forbidding("Ui.Button", "Db").is_some());
+
+    // no rules file means no rules
+    let elsewhere = LayerRules::from_dir(&sources, Path::new("/elsewhere")).unwrap();
+
+    assert!(elsewhere.is_empty());
+}
+
+#[test]
+fn filename_case_mismatch() {
+    let root = Path::new("/not/on/disk");
+    let sources = InMemorySources(hashmap! {
+        root.join("Http").join("client.roc") => "",
+        root.join("Parser.roc") => "",
+    });
+    let source_roots = SourceRoots::from_dir(&sources, root).unwrap();
+    let case_mismatch = |path: PathBuf| source_roots.case_mismatch(&sources, root, &path);
+
+    assert_eq!(case_mismatch(root.join("Parser.roc")), None);
+    assert_eq!(
+        case_mismatch(root.join("Http").join("Client.roc")),
+        Some(root.join("Http").join("client.roc"))
+    );
+    assert_eq!(
+        case_mismatch(root.join("HTTP").join("Client.roc")),
+        Some(root.join("Http").join("client.roc"))
+    );
+    // missing files are reported elsewhere
+    assert_eq!(case_mismatch(root.join("Json.roc")), None);
+}
+
+#[test]
+fn import_with_wrong_casing() {
+    let dir = PathBuf::from("/not/on/disk");
+    let sources = InMemorySources(hashmap! {
+        dir.join("Main.roc") => "interface Main exposes [] imports [Http.Client]\n",
+        dir.join("http").join("client.roc") => "interface Http.Client exposes [] imports []\n",
+    });
+
+    let report =
+        load_through_provider(dir.join("Main.roc"), sources, DEFAULT_MAX_FILE_SIZE).unwrap_err();
+
+    assert!(report.contains("FILENAME CASE MISMATCH"), "{report}");
+    assert!(report.contains("http/client.roc"), "{report}");
+}
+
+/// Like [InMemorySources], but paths are looked up ignoring case, the way the default file
+/// systems of macOS and Windows do. Listing a directory gives the names as they are spelled.
+struct CaseInsensitiveSources(InMemorySources);
+
+impl CaseInsensitiveSources {
+    fn spelled(&self, path: &Path) -> Option<&Path> {
+        let CaseInsensitiveSources(InMemorySources(files)) = self;
+        let wanted = path.to_string_lossy().to_lowercase();
+
+        files
+            .keys()
+            .find(|key| key.to_string_lossy().to_lowercase() == wanted)
+            .map(PathBuf::as_path)
+    }
+}
+
+impl SourceProvider for CaseInsensitiveSources {
+    fn read(&self, path: &Path) -> std::io::Result<Cow<'_, [u8]>> {
+        match self.spelled(path) {
+            Some(spelled) => self.0.read(spelled),
+            None => Err(std::io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.spelled(path).is_some()
+    }
+
+    fn modified_time(&self, path: &Path) -> std::io::Result<SystemTime> {
+        self.0.modified_time(path)
+    }
+
+    fn list_dir(&self, dir: &Path) -> std::io::Result<Vec<DirEntry>> {
+        self.0.list_dir(dir)
+    }
+}
+
+#[test]
+fn import_with_wrong_casing_on_case_insensitive_file_system() {
+    let dir = PathBuf::from("/not/on/disk");
+    let sources = CaseInsensitiveSources(InMemorySources(hashmap! {
+        dir.join("Main.roc") => "interface Main exposes [] imports [Http.Client, Json]\n",
+        dir.join("http").join("client.roc") => "interface Http.Client exposes [] imports []\n",
+        dir.join("Json.roc") => "interface Json exposes [] imports []\n",
+    }));
+
+    // the file opens fine, but the same project would not load on a case-sensitive file system
+    assert!(sources.exists(&dir.join("Http").join("Client.roc")));
+
+    let report =
+        load_through_provider(dir.join("Main.roc"), sources, DEFAULT_MAX_FILE_SIZE).unwrap_err();
+
+    assert!(report.contains("FILENAME CASE MISMATCH"), "{report}");
+    assert!(report.contains("http/client.roc"), "{report}");
+    assert!(!report.contains("Json.roc"), "{report}");
+}
+
+#[test]
+fn provided_type_does_not_match_platform_requires() {
+    use ven_pretty::DocAllocator;
+
+    let modules = vec![
+        (
+            "platform/main.roc",
+            indoc!(
+                r#"
+                platform "test-platform"
+                    requires {} { main : Str }
+                    exposes []
+                    packages {}
+                    imports []
+                    provides [mainForHost]
+
+                mainForHost : Str
+                mainForHost = main
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                app "test-app"
+                    packages { pf: "platform/main.roc" }
+                    imports []
+                    provides [main] to pf
+
+                main = 42
+                "#
+            ),
+        ),
+    ];
+
+    let arena = Bump::new();
+    let mut loaded = multiple_modules_help(
+        "provided_type_does_not_match_platform_requires",
+        &arena,
+        modules,
+        LayerRules::default(),
+    )
+    .unwrap()
+    .unwrap();
+
+    let (module_id, problems) = loaded
+        .type_problems
+        .drain()
+        .find(|(_, problems)| !problems.is_empty())
+        .expect("the platform module should have a type problem");
+    let (filename, src) = &loaded.sources[&module_id];
+
+    let src_lines: Vec<&str> = src.split('\n').collect();
+    let lines = LineInfo::new(src);
+    let alloc = RocDocAllocator::new(&src_lines, module_id, &loaded.interns);
+    let reports = problems.into_iter().flat_map(|problem| {
+        roc_reporting::report::type_problem(&alloc, &lines, filename.clone(), problem)
+            .map(|report| report.pretty(&alloc))
+    });
+
+    let mut report = String::new();
+    alloc
+        .stack(reports)
+        .1
+        .render_raw(70, &mut roc_reporting::report::CiWrite::new(&mut report))
+        .unwrap();
+
+    assert!(report.contains("PLATFORM REQUIREMENT MISMATCH"), "{report}");
+    assert!(report.contains("The app provides `main`"), "{report}");
+    assert!(
+        report.contains("But the platform requires it to be:"),
+        "{report}"
+    );
+}
+
+#[test]
+fn invalid_utf8() {
+    let dir = roc_test_utils::TmpDir::new("tmp/invalid_utf8");
+    let filename = dir.path().join("Main.roc");
+
+    // "café" saved as Latin-1
+    std::fs::write(
+        &filename,
+        b"interface Main exposes [] imports []\n\nx = \"caf\xe9\"\n",
+    )
+    .unwrap();
+
+    let arena = Bump::new();
+    let err = load_and_typecheck(
+        &arena,
+        filename,
+        Default::default(),
+        TARGET_INFO,
+        FunctionKind::LambdaSet,
+        LayerRules::default(),
+    )
+    .unwrap_err();
+
+    match err {
+        LoadingProblem::FormattedReport(report) => {
+            assert!(report.contains("INVALID UTF-8"), "{report}");
+            assert!(report.contains("line 3, column 9"), "{report}");
+            assert!(report.contains("byte offset 46"), "{report}");
+        }
+        other => panic!("expected an invalid UTF-8 report, but got {other:?}"),
+    }
+}
+
+#[test]
+fn occurrences_of_symbols() {
+    let modules = vec![
+        (
+            "Geometry",
+            indoc!(
+                r#"
+                interface Geometry exposes [Point, origin] imports []
+
+                Point : (F64, F64)
+
+                origin : Point
+                origin = (0, 0)
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                interface Main exposes [main, swap] imports [Geometry]
+
+                main : Geometry.Point
+                main = swap Geometry.origin
+
+                swap = \(x, y) -> (y, x)
+                "#
+            ),
+        ),
+    ];
+
+    let loaded = multiple_modules("occurrences_of_symbols", modules).unwrap();
+    let src = &loaded.sources[&loaded.module_id].1;
+
+    let occurrences: Vec<(&str, &str)> = loaded.occurrences[&loaded.module_id]
+        .iter()
+        .map(|occurrence| {
+            let region = occurrence.region;
+            let text = &src[region.start().offset as usize..region.end().offset as usize];
+
+            (occurrence.value.as_str(&loaded.interns), text)
+        })
+        .collect();
+
+    assert_eq!(
+        occurrences,
+        vec![
+            ("Point", "Geometry.Point"),
+            ("swap", "swap"),
+            ("origin", "Geometry.origin"),
+            ("y", "y"),
+            ("x", "x"),
+        ]
+    );
+}
+
+#[test]
+fn bindings_of_symbols() {
+    let modules = vec![(
+        "Main",
+        indoc!(
+            r#"
+            interface Main exposes [area, count] imports []
+
+            area : { width : F64, height ? F64 } -> F64
+            area = \{ width, height ? 1 } -> width * height
+
+            count : List   Str -> Nat
+            count = \list ->
+                when list is
+                    [_, .. as rest] -> 1 + count rest
+                    [] as empty -> List.len empty
+            "#
+        ),
+    )];
+
+    let loaded = multiple_modules("bindings_of_symbols", modules).unwrap();
+    let src = &loaded.sources[&loaded.module_id].1;
+    let named = |locs: &[Loc<Symbol>]| -> Vec<(&str, &str)> {
+        locs.iter()
+            .map(|loc| {
+                let region = loc.region;
+                let text = &src[region.start().offset as usize..region.end().offset as usize];
+
+                (loc.value.as_str(&loaded.interns), text)
+            })
+            .collect()
+    };
+
+    assert_eq!(
+        named(&loaded.bindings[&loaded.module_id]),
+        vec![
+            ("area", "area"),
+            ("area", "area"),
+            ("width", "width"),
+            ("height", "height"),
+            ("count", "count"),
+            ("count", "count"),
+            ("list", "list"),
+            ("rest", "rest"),
+            ("empty", "empty"),
+        ]
+    );
+
+    // only the name of an applied type, however far away its arguments are
+    let lists: Vec<_> = named(&loaded.occurrences[&loaded.module_id])
+        .into_iter()
+        .filter(|(name, _)| *name == "List")
+        .collect();
+
+    assert_eq!(lists, vec![("List", "List")]);
+}
+
+#[test]
+fn doc_comments_of_top_level_defs() {
+    let modules = vec![(
+        "Main",
+        indoc!(
+            r#"
+            interface Main exposes [Color, red, blue, green] imports []
+
+            ## A color
+            Color : [Red, Green, Blue]
+
+            ## The first color
+            red : Color
+            red = Red
+
+            # not a doc comment
+            blue = Blue
+
+            ## A doc comment
+            ## over two lines
+            green = Green
+            "#
+        ),
+    )];
+
+    let loaded = multiple_modules("doc_comments_of_top_level_defs", modules).unwrap();
+
+    let doc_comments: HashMap<&str, &str> = loaded.doc_comments[&loaded.module_id]
+        .iter()
+        .map(|(symbol, docs)| (symbol.as_str(&loaded.interns), docs.as_str()))
+        .collect();
+
+    assert_eq!(
+        doc_comments,
+        HashMap::from([
+            ("Color", "A color\n"),
+            ("red", "The first color\n"),
+            ("green", "A doc comment\nover two lines\n"),
+        ])
+    );
+}
+
+#[test]
+fn inferred_types_of_top_level_values() {
+    let modules = vec![
+        (
+            "Geometry",
+            indoc!(
+                r#"
+                interface Geometry exposes [scale] imports []
+
+                scale = \x, factor -> x * factor
+                "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                interface Main exposes [greeting, twice] imports [Geometry]
+
+                greeting = "hi"
+
+                twice = \x -> Geometry.scale x 2
+                "#
+            ),
+        ),
+    ];
+
+    let mut loaded = multiple_modules("inferred_types_of_top_level_values", modules).unwrap();
+    let inferred_types = loaded.inferred_types();
+    let interns = &loaded.interns;
+
+    let types: HashMap<String, &str> = inferred_types
+        .iter()
+        .map(|(symbol, type_str)| {
+            let name = format!(
+                "{}.{}",
+                symbol.module_string(interns),
+                symbol.as_str(interns)
+            );
+
+            (name, type_str.as_str())
+        })
+        .collect();
+
+    assert_eq!(types["Geometry.scale"], "Num a, Num a -> Num a");
+    assert_eq!(types["Main.greeting"], "Str");
+    assert_eq!(types["Main.twice"], "Num a -> Num a");
+}
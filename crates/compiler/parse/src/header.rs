@@ -23,6 +23,42 @@ impl<'a> HeaderType<'a> {
             HeaderType::Platform { .. } | HeaderType::Package { .. } => &[],
         }
     }
+
+    pub fn role(&self) -> HeaderRole {
+        match self {
+            HeaderType::App { .. } => HeaderRole::App,
+            HeaderType::Hosted { .. } => HeaderRole::Hosted,
+            HeaderType::Builtin { .. } => HeaderRole::Builtin,
+            HeaderType::Package { .. } => HeaderRole::Package,
+            HeaderType::Platform { .. } => HeaderRole::Platform,
+            HeaderType::Interface { .. } => HeaderRole::Interface,
+        }
+    }
+}
+
+/// The kind of header a module has, without its contents
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum HeaderRole {
+    App,
+    Hosted,
+    Builtin,
+    Package,
+    Platform,
+    Interface,
+}
+
+impl HeaderRole {
+    /// How reports refer to a module with this header, e.g. `platform`
+    pub fn keyword(self) -> &'static str {
+        match self {
+            HeaderRole::App => "app",
+            HeaderRole::Hosted => "hosted",
+            HeaderRole::Builtin => "builtin",
+            HeaderRole::Package => "package",
+            HeaderRole::Platform => "platform",
+            HeaderRole::Interface => "interface",
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -10,13 +10,14 @@ use crate::ident::{self, lowercase_ident, unqualified_ident, uppercase, Uppercas
 use crate::parser::Progress::{self, *};
 use crate::parser::{
     backtrackable, increment_min_indent, optional, reset_min_indent, specialize, word1, word2,
-    EExposes, EGenerates, EGeneratesWith, EHeader, EImports, EPackages, EProvides, ERequires,
-    ETypedIdent, Parser, SourceError, SpaceProblem, SyntaxError,
+    EExposes, EExpr, EGenerates, EGeneratesWith, EHeader, EImports, EPackages, EProvides,
+    ERequires, ETypedIdent, Parser, SourceError, SpaceProblem, SyntaxError,
 };
 use crate::state::State;
 use crate::string_literal::{self, parse_str_literal};
 use crate::type_annotation;
-use roc_region::all::{Loc, Position};
+use roc_collections::soa::Slice;
+use roc_region::all::{Loc, Position, Region};
 
 fn end_of_file<'a>() -> impl Parser<'a, (), SyntaxError<'a>> {
     |_arena, state: State<'a>, _min_indent: u32| {
@@ -36,6 +37,68 @@ pub fn module_defs<'a>() -> impl Parser<'a, Defs<'a>, SyntaxError<'a>> {
     )
 }
 
+/// Like [module_defs], but a def that fails to parse doesn't stop the rest of the module from
+/// being parsed. The parser skips ahead to the next top-level def (the next line that starts
+/// with a name or a comment in the first column), records the problem, and carries on.
+///
+/// This gives editors a partial AST to work with while the user is halfway through typing.
+/// The problems are in source order, each with the region of source that was skipped, and
+/// when there are none the defs are exactly what [module_defs] would have returned.
+pub fn module_defs_with_recovery<'a>(
+    arena: &'a bumpalo::Bump,
+    state: State<'a>,
+) -> (Defs<'a>, Vec<Loc<SyntaxError<'a>>>) {
+    use crate::expr::{parse_single_def, push_single_def, ExprParseOptions};
+
+    let options = ExprParseOptions {
+        accept_multi_backpassing: true,
+        check_for_arrow: true,
+    };
+    let is_def_start = |byte: u8| byte.is_ascii_alphabetic() || byte == b'_' || byte == b'#';
+
+    let mut defs = Defs::default();
+    let mut problems = Vec::new();
+    let mut state = state;
+    let mut start_column = None;
+
+    loop {
+        let (spaces, def_start) = match space0_e(EExpr::IndentStart).parse(arena, state.clone(), 0)
+        {
+            Ok((_, spaces, after_spaces)) => (spaces, after_spaces),
+            Err(_) => (&[] as &[_], state.clone()),
+        };
+
+        if def_start.has_reached_end() {
+            // like module_defs, keep trailing comments after the last def
+            if let Some(last) = defs.space_after.last_mut() {
+                *last = Slice::extend_new(&mut defs.spaces, spaces.iter().copied());
+            }
+
+            break;
+        }
+
+        // like module_defs, every def is parsed with the indentation of the first one
+        let min_indent = *start_column.get_or_insert(def_start.column());
+
+        let problem = match parse_single_def(options, min_indent, arena, state) {
+            Ok((_, Some(single_def), next_state)) => {
+                push_single_def(arena, &mut defs, single_def);
+                state = next_state;
+                continue;
+            }
+            // something that isn't a def, like a stray expression
+            Ok((_, None, _)) => SyntaxError::NotEndOfFile(def_start.pos()),
+            Err((_, fail)) => SyntaxError::Expr(fail, def_start.pos()),
+        };
+
+        let skipped_from = def_start.pos();
+        state = def_start.skip_to_next_line_starting_with(is_def_start);
+        problems.push(Loc::at(Region::new(skipped_from, state.pos()), problem));
+    }
+
+    (defs, problems)
+}
+
 pub fn parse_header<'a>(
     arena: &'a bumpalo::Bump,
     state: State<'a>,
@@ -112,6 +112,26 @@ impl<'a> State<'a> {
         self
     }
 
+    /// Skip ahead to the start of the next line whose first byte satisfies `is_line_start`,
+    /// or to the end of the input if there is no such line. The current line is always skipped.
+    #[must_use]
+    pub(crate) fn skip_to_next_line_starting_with(
+        mut self,
+        is_line_start: impl Fn(u8) -> bool,
+    ) -> State<'a> {
+        let bytes = self.bytes();
+        let skipped = bytes
+            .windows(2)
+            .position(|pair| pair[0] == b'\n' && is_line_start(pair[1]))
+            .map_or(bytes.len(), |newline| newline + 1);
+
+        self.offset += skipped;
+        self.line_start = self.pos();
+        self.line_start_after_whitespace = self.line_start;
+
+        self
+    }
+
     /// Returns the current position
     pub const fn pos(&self) -> Position {
         Position::new(self.offset as u32)
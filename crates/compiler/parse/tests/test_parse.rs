@@ -22,7 +22,7 @@ mod test_parse {
     use roc_parse::ast::StrSegment::*;
     use roc_parse::ast::{self, EscapedChar};
     use roc_parse::ast::{CommentOrNewline, StrLiteral::*};
-    use roc_parse::module::module_defs;
+    use roc_parse::module::{module_defs, module_defs_with_recovery};
     use roc_parse::parser::{Parser, SyntaxError};
     use roc_parse::state::State;
     use roc_parse::test_helpers::parse_expr_with;
@@ -317,6 +317,68 @@ mod test_parse {
         }
     }
 
+    #[test]
+    fn recover_from_broken_def() {
+        let arena = &Bump::new();
+        let src = indoc!(
+            r#"
+            x = 1
+
+            y = (
+
+            z : Str
+            z = "z"
+            "#
+        );
+
+        let (defs, problems) = module_defs_with_recovery(arena, State::new(src.as_bytes()));
+
+        // `z`'s annotation and body are still joined up
+        assert_eq!(defs.len(), 2);
+        assert_eq!(
+            defs.regions[1].start().offset as usize,
+            src.find("z :").unwrap()
+        );
+
+        match problems.as_slice() {
+            [Loc {
+                region,
+                value: SyntaxError::Expr(_, pos),
+            }] => {
+                assert_eq!(pos.offset as usize, src.find("y =").unwrap());
+                // everything up to `z` was skipped
+                assert_eq!(region.start(), *pos);
+                assert_eq!(region.end().offset as usize, src.find("z :").unwrap());
+            }
+            other => panic!("expected one problem in `y`, but got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn recovery_without_problems_matches_module_defs() {
+        let arena = &Bump::new();
+        let src = indoc!(
+            r#"
+            # the answer
+            x : U64
+            x = 42
+
+            Point : { x : F64, y : F64 }
+
+            main = Num.toStr x
+            # trailing comment
+            "#
+        );
+
+        let (_, expected, _) = module_defs()
+            .parse(arena, State::new(src.as_bytes()), 0)
+            .unwrap();
+        let (actual, problems) = module_defs_with_recovery(arena, State::new(src.as_bytes()));
+
+        assert!(problems.is_empty(), "{problems:?}");
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn parse_expr_size() {
         assert_eq!(std::mem::size_of::<roc_parse::ast::Expr>(), 40);
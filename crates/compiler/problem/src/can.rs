@@ -12,6 +12,14 @@ use roc_types::types::AliasKind;
 
 use crate::Severity;
 
+/// A value another module exposes under a name that is not in scope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImportCandidate {
+    pub symbol: Symbol,
+    /// Whether the module exposing it is already imported, so qualifying the name is enough
+    pub imported: bool,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct CycleEntry {
     pub symbol: Symbol,
@@ -27,6 +35,8 @@ pub enum BadPattern {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ShadowKind {
     Variable,
+    /// A value imported by name from a builtin module, e.g. `concat` in `imports [List.{ concat }]`
+    BuiltinImport(Symbol),
     Alias(Symbol),
     Opaque(Symbol),
     Ability(Symbol),
@@ -38,7 +48,20 @@ pub enum Problem {
     UnusedDef(Symbol, Region),
     UnusedImport(Symbol, Region),
     UnusedModuleImport(ModuleId, Region),
-    ExposedButNotDefined(Symbol),
+    /// An import that the project's layering rules don't allow
+    ForbiddenImport {
+        imported: ModuleId,
+        region: Region,
+        rule: String,
+    },
+    /// A use of something its module marked as deprecated, with the message it gave (if any)
+    Deprecated {
+        symbol: Symbol,
+        region: Region,
+        message: String,
+    },
+    /// The region is where the header exposes it
+    ExposedButNotDefined(Symbol, Region),
     UnknownGeneratesWith(Loc<Ident>),
     /// First symbol is the name of the closure with that argument
     /// Bool is whether the closure is anonymous
@@ -96,6 +119,9 @@ pub enum Problem {
     SignatureDefMismatch {
         annotation_pattern: Region,
         def_pattern: Region,
+        /// The names the annotation and the definition are for, if they are plain identifiers
+        annotation_name: Option<Ident>,
+        def_name: Option<Ident>,
     },
     InvalidAliasRigid {
         alias_name: Symbol,
@@ -211,6 +237,14 @@ pub enum Problem {
         filename: PathBuf,
         error: io::ErrorKind,
     },
+    /// A top-level def that didn't parse. The parser skipped over it, so the rest of the module
+    /// is still checked. The error borrows from the arena the module was parsed in, so its
+    /// report is rendered as soon as it's found.
+    SyntaxError {
+        title: String,
+        message: String,
+        region: Region,
+    },
 }
 
 impl Problem {
@@ -221,7 +255,9 @@ impl Problem {
             Problem::UnusedDef(_, _) => Warning,
             Problem::UnusedImport(_, _) => Warning,
             Problem::UnusedModuleImport(_, _) => Warning,
-            Problem::ExposedButNotDefined(_) => RuntimeError,
+            Problem::ForbiddenImport { .. } => RuntimeError,
+            Problem::Deprecated { .. } => Warning,
+            Problem::ExposedButNotDefined(_, _) => RuntimeError,
             Problem::UnknownGeneratesWith(_) => RuntimeError,
             Problem::UnusedArgument(_, _, _, _) => Warning,
             Problem::UnusedBranchDef(_, _) => Warning,
@@ -277,6 +313,7 @@ impl Problem {
             Problem::OverAppliedCrash { .. } => RuntimeError,
             Problem::DefsOnlyUsedInRecursion(_, _) => Warning,
             Problem::FileProblem { .. } => Fatal,
+            Problem::SyntaxError { .. } => RuntimeError,
         }
     }
 
@@ -295,6 +332,9 @@ impl Problem {
             }
             | Problem::UnusedImport(_, region)
             | Problem::UnusedModuleImport(_, region)
+            | Problem::ForbiddenImport { region, .. }
+            | Problem::Deprecated { region, .. }
+            | Problem::ExposedButNotDefined(_, region)
             | Problem::UnknownGeneratesWith(Loc { region, .. })
             | Problem::UnusedArgument(_, _, _, region)
             | Problem::UnusedBranchDef(_, region)
@@ -338,6 +378,8 @@ impl Problem {
             | Problem::RuntimeError(RuntimeError::LookupNotInScope {
                 loc_name: Loc { region, .. },
                 suggestion_options: _,
+                qualified_suggestions: _,
+                import_candidate: _,
                 underscored_suggestion_region: _,
             })
             | Problem::RuntimeError(RuntimeError::OpaqueNotDefined {
@@ -416,6 +458,7 @@ impl Problem {
             | Problem::UnnecessaryOutputWildcard { region }
             | Problem::OverAppliedCrash { region }
             | Problem::UnappliedCrash { region }
+            | Problem::SyntaxError { region, .. }
             | Problem::DefsOnlyUsedInRecursion(_, region) => Some(*region),
             Problem::RuntimeError(RuntimeError::CircularDef(cycle_entries))
             | Problem::BadRecursion(cycle_entries) => {
@@ -428,8 +471,7 @@ impl Problem {
             | Problem::RuntimeError(RuntimeError::VoidValue)
             | Problem::RuntimeError(RuntimeError::ExposedButNotDefined(_))
             | Problem::RuntimeError(RuntimeError::NoImplementationNamed { .. })
-            | Problem::FileProblem { .. }
-            | Problem::ExposedButNotDefined(_) => None,
+            | Problem::FileProblem { .. } => None,
         }
     }
 }
@@ -521,6 +563,11 @@ pub enum RuntimeError {
         loc_name: Loc<Ident>,
         /// All of the names in scope (for the error message)
         suggestion_options: MutSet<Box<str>>,
+        /// Values that imported modules expose under a similar name, like `Bool.true` for `true`
+        qualified_suggestions: Vec<Box<str>>,
+        /// If exactly one known module exposes a value with this exact name, that value.
+        /// Importing the module and qualifying the name would make the lookup work.
+        import_candidate: Option<ImportCandidate>,
         /// If the unfound variable is `name` and there's an ignored variable called `_name`,
         /// this is the region where `_name` is defined (for the error message)
         underscored_suggestion_region: Option<Region>,
@@ -364,16 +364,33 @@ where
     }
 }
 
+/// Converts between the byte offsets in a [Region] and line/column positions.
+///
+/// Columns count bytes, except in the `_utf16` conversions, where they count UTF-16 code units.
+/// That is what LSP clients count in by default.
 #[derive(Debug)]
 pub struct LineInfo {
     line_offsets: Vec<u32>,
+    /// The byte offset of every non-ASCII character, and how many more bytes it takes in UTF-8
+    /// than code units in UTF-16
+    wide_chars: Vec<(u32, u32)>,
 }
 
 impl LineInfo {
     pub fn new(src: &str) -> LineInfo {
         let mut line_offsets = vec![0];
         line_offsets.extend(src.match_indices('\n').map(|(offset, _)| offset as u32 + 1));
-        LineInfo { line_offsets }
+
+        let wide_chars = src
+            .char_indices()
+            .filter(|(_, c)| !c.is_ascii())
+            .map(|(offset, c)| (offset as u32, (c.len_utf8() - c.len_utf16()) as u32))
+            .collect();
+
+        LineInfo {
+            line_offsets,
+            wide_chars,
+        }
     }
 
     pub fn convert_offset(&self, offset: u32) -> LineColumn {
@@ -411,6 +428,59 @@ impl LineInfo {
         Region::new(start, end)
     }
 
+    pub fn convert_pos_utf16(&self, pos: Position) -> LineColumn {
+        let LineColumn { line, column } = self.convert_pos(pos);
+        let line_start = self.line_offsets[line as usize];
+
+        let extra_bytes: u32 = self
+            .wide_chars_from(line_start)
+            .take_while(|(offset, _)| *offset < pos.offset)
+            .map(|(_, extra)| extra)
+            .sum();
+
+        LineColumn {
+            line,
+            column: column - extra_bytes,
+        }
+    }
+
+    pub fn convert_region_utf16(&self, region: Region) -> LineColumnRegion {
+        LineColumnRegion {
+            start: self.convert_pos_utf16(region.start()),
+            end: self.convert_pos_utf16(region.end()),
+        }
+    }
+
+    pub fn convert_line_column_utf16(&self, lc: LineColumn) -> Position {
+        let line_start = self.line_offsets[lc.line as usize];
+        let mut offset = line_start + lc.column;
+
+        // every wide character before the position pushes it further along in bytes
+        for (char_offset, extra) in self.wide_chars_from(line_start) {
+            if char_offset < offset {
+                offset += extra;
+            } else {
+                break;
+            }
+        }
+
+        Position::new(offset)
+    }
+
+    pub fn convert_line_column_region_utf16(&self, lc_region: LineColumnRegion) -> Region {
+        let start = self.convert_line_column_utf16(lc_region.start);
+        let end = self.convert_line_column_utf16(lc_region.end);
+        Region::new(start, end)
+    }
+
+    fn wide_chars_from(&self, offset: u32) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let first = self
+            .wide_chars
+            .partition_point(|(char_offset, _)| *char_offset < offset);
+
+        self.wide_chars[first..].iter().copied()
+    }
+
     pub fn num_lines(&self) -> u32 {
         self.line_offsets.len() as u32
     }
@@ -476,3 +546,33 @@ fn test_line_info() {
 
     check_correctness(&["", ""]);
 }
+
+#[test]
+fn test_line_info_utf16() {
+    // é is 2 bytes and 1 code unit, 🎉 is 4 bytes and 2 code units
+    let src = "x = \"é🎉\"\ny = 🎉 + z";
+    let info = LineInfo::new(src);
+
+    let utf16_column = |needle: &str| {
+        let pos = Position::new(src.find(needle).unwrap() as u32);
+        let lc = info.convert_pos_utf16(pos);
+
+        assert_eq!(info.convert_line_column_utf16(lc), pos, "{needle}");
+
+        (lc.line, lc.column)
+    };
+
+    assert_eq!(utf16_column("x"), (0, 0));
+    assert_eq!(utf16_column("é"), (0, 5));
+    assert_eq!(utf16_column("🎉\""), (0, 6));
+    assert_eq!(utf16_column("\"\n"), (0, 8));
+    assert_eq!(utf16_column("y"), (1, 0));
+    assert_eq!(utf16_column("+"), (1, 7));
+    assert_eq!(utf16_column("z"), (1, 9));
+
+    // byte columns are unaffected
+    assert_eq!(
+        info.convert_pos(Position::new(src.find('+').unwrap() as u32)),
+        LineColumn { line: 1, column: 9 }
+    );
+}
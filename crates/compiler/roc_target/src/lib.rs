@@ -130,13 +130,34 @@ pub enum PtrWidth {
 }
 
 /// These should be sorted alphabetically!
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, EnumCount)]
+///
+/// The string forms (e.g. `x86_64`) are the suffixes of per-architecture modules, like
+/// `Foo.x86_64.roc`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumIter,
+    EnumCount,
+    EnumString,
+    IntoStaticStr,
+)]
 #[repr(u8)]
 pub enum Architecture {
+    #[strum(serialize = "aarch32")]
     Aarch32,
+    #[strum(serialize = "aarch64")]
     Aarch64,
+    #[strum(serialize = "wasm32")]
     Wasm32,
+    #[strum(serialize = "x86_32")]
     X86_32,
+    #[strum(serialize = "x86_64")]
     X86_64,
 }
 
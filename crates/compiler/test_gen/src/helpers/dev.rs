@@ -57,12 +57,12 @@ pub fn helper(
     }
 
     let load_config = LoadConfig {
-        target_info: roc_target::TargetInfo::default_x86_64(),
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
-        palette: roc_reporting::report::DEFAULT_PALETTE,
         threading: Threading::Single,
-        exec_mode: ExecutionMode::Executable,
-        function_kind: FunctionKind::LambdaSet,
+        ..LoadConfig::new(
+            roc_target::TargetInfo::default_x86_64(),
+            FunctionKind::LambdaSet,
+            ExecutionMode::Executable,
+        )
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -15,7 +15,7 @@ use roc_load::{
 use roc_mono::ir::{CrashTag, OptLevel, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
 use roc_region::all::LineInfo;
-use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+use roc_reporting::report::DEFAULT_PALETTE;
 use target_lexicon::Triple;
 
 #[cfg(feature = "gen-llvm-wasm")]
@@ -72,12 +72,8 @@ fn create_llvm_module<'a>(
     }
 
     let load_config = LoadConfig {
-        target_info,
-        function_kind,
-        render: RenderTarget::ColorTerminal,
-        palette: DEFAULT_PALETTE,
         threading: Threading::Single,
-        exec_mode: ExecutionMode::Executable,
+        ..LoadConfig::new(target_info, function_kind, ExecutionMode::Executable)
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -140,7 +136,7 @@ fn create_llvm_module<'a>(
                 | UnusedModuleImport(_, _)
                 | RuntimeError(_)
                 | UnsupportedPattern(_, _)
-                | ExposedButNotDefined(_) => {
+                | ExposedButNotDefined(_, _) => {
                     let report = can_problem(&alloc, &line_info, module_path.clone(), problem);
                     let mut buf = String::new();
 
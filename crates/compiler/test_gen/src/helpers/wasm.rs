@@ -88,12 +88,13 @@ fn compile_roc_to_wasm_bytes<'a, T: Wasm32Result>(
     }
 
     let load_config = LoadConfig {
-        target_info: roc_target::TargetInfo::default_wasm32(),
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
         palette: DEFAULT_PALETTE_HTML,
         threading: Threading::Single,
-        exec_mode: ExecutionMode::Executable,
-        function_kind: FunctionKind::LambdaSet,
+        ..LoadConfig::new(
+            roc_target::TargetInfo::default_wasm32(),
+            FunctionKind::LambdaSet,
+            ExecutionMode::Executable,
+        )
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -104,13 +104,10 @@ fn compiles_to_ir(test_name: &str, src: &str, mode: &str, allow_type_errors: boo
     }
 
     let load_config = LoadConfig {
-        target_info: TARGET_INFO,
-        // TODO parameterize
-        function_kind: FunctionKind::LambdaSet,
         threading: Threading::Single,
         render: roc_reporting::report::RenderTarget::Generic,
-        palette: roc_reporting::report::DEFAULT_PALETTE,
-        exec_mode,
+        // TODO parameterize function_kind
+        ..LoadConfig::new(TARGET_INFO, FunctionKind::LambdaSet, exec_mode)
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
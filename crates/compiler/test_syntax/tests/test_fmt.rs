@@ -4767,6 +4767,51 @@ mod test_fmt {
 
     // MODULES
 
+    #[test]
+    fn format_range_only_touches_overlapping_defs() {
+        use roc_fmt::{format_range, Ast};
+        use roc_region::all::{Position, Region};
+
+        let arena = Bump::new();
+        let src = indoc!(
+            r#"
+            interface Foo exposes [a, b, c] imports []
+
+            a =   1
+
+            # b is two
+            b =   2
+
+            c =   3
+            "#
+        );
+
+        let ast = Ast::parse(&arena, src).unwrap();
+        let start = src.find("b =").unwrap() as u32;
+        let range = Region::new(Position::new(start), Position::new(start + 1));
+
+        let (region, formatted) = format_range(&arena, &ast, range).unwrap();
+        let start = region.start().offset as usize;
+        let end = region.end().offset as usize;
+        let actual = format!("{}{formatted}{}", &src[..start], &src[end..]);
+
+        assert_multiline_str_eq!(
+            indoc!(
+                r#"
+                interface Foo exposes [a, b, c] imports []
+
+                a =   1
+
+                # b is two
+                b = 2
+
+                c =   3
+                "#
+            ),
+            actual.as_str()
+        );
+    }
+
     #[test]
     fn single_line_interface() {
         module_formats_same(indoc!(
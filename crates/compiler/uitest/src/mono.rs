@@ -43,12 +43,13 @@ pub(crate) fn write_compiled_ir<'a>(
     let file_path = dir.path().join(filename);
 
     let load_config = LoadConfig {
-        target_info: roc_target::TargetInfo::default_x86_64(),
-        function_kind: compiler_settings.function_kind,
         threading: Threading::Single,
         render: roc_reporting::report::RenderTarget::Generic,
-        palette: roc_reporting::report::DEFAULT_PALETTE,
-        exec_mode,
+        ..LoadConfig::new(
+            roc_target::TargetInfo::default_x86_64(),
+            compiler_settings.function_kind,
+            exec_mode,
+        )
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -4,10 +4,10 @@ extern crate pulldown_cmark;
 extern crate roc_load;
 use bumpalo::Bump;
 use roc_can::scope::Scope;
-use roc_collections::VecSet;
+use roc_collections::{MutMap, VecSet};
 use roc_load::docs::{DocEntry, TypeAnnotation};
 use roc_load::docs::{ModuleDocumentation, RecordField};
-use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
+use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem};
 use roc_module::symbol::{Interns, Symbol};
 use roc_packaging::cache::{self, RocCacheDir};
 use roc_parse::ident::{parse_ident, Accessor, Ident};
@@ -20,7 +20,8 @@ use std::path::{Path, PathBuf};
 const LINK_SVG: &str = include_str!("./static/link.svg");
 
 pub fn generate_docs_html(root_file: PathBuf, build_dir: &Path) {
-    let loaded_module = load_module_for_docs(root_file);
+    let mut loaded_module = load_module_for_docs(root_file);
+    let inferred_types = loaded_module.inferred_types();
 
     // TODO get these from the platform's source file rather than hardcoding them!
     // github.com/roc-lang/roc/issues/5712
@@ -164,8 +165,13 @@ pub fn generate_docs_html(root_file: PathBuf, build_dir: &Path) {
             )
             .replace(
                 "<!-- Module Docs -->",
-                render_module_documentation(module_docs, &loaded_module, &all_exposed_symbols)
-                    .as_str(),
+                render_module_documentation(
+                    module_docs,
+                    &loaded_module,
+                    &all_exposed_symbols,
+                    &inferred_types,
+                )
+                .as_str(),
             );
 
         fs::write(module_dir.join("index.html"), rendered_module)
@@ -220,6 +226,7 @@ fn render_module_documentation(
     module: &ModuleDocumentation,
     root_module: &LoadedModule,
     all_exposed_symbols: &VecSet<Symbol>,
+    inferred_types: &MutMap<Symbol, String>,
 ) -> String {
     let mut buf = String::new();
     let module_name = module.name.as_str();
@@ -262,6 +269,10 @@ fn render_module_documentation(
                         content.push(' ');
 
                         type_annotation_to_html(0, &mut content, type_ann, false);
+                    } else if let Some(type_str) = inferred_types.get(&doc_def.symbol) {
+                        // Without an annotation, show the type the compiler inferred instead
+                        content.push_str(" : ");
+                        content.push_str(type_str);
                     }
 
                     push_html(
@@ -434,14 +445,11 @@ fn render_sidebar<'a, I: Iterator<Item = &'a ModuleDocumentation>>(modules: I) -
 
 pub fn load_module_for_docs(filename: PathBuf) -> LoadedModule {
     let arena = Bump::new();
-    let load_config = LoadConfig {
-        target_info: roc_target::TargetInfo::default_x86_64(), // This is just type-checking for docs, so "target" doesn't matter
-        function_kind: roc_solve::FunctionKind::LambdaSet,
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
-        palette: roc_reporting::report::DEFAULT_PALETTE,
-        threading: Threading::AllAvailable,
-        exec_mode: ExecutionMode::Check,
-    };
+    let load_config = LoadConfig::new(
+        roc_target::TargetInfo::default_x86_64(), // This is just type-checking for docs, so "target" doesn't matter
+        roc_solve::FunctionKind::LambdaSet,
+        ExecutionMode::Check,
+    );
     match roc_load::load_and_typecheck(
         &arena,
         filename,
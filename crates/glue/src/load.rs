@@ -15,7 +15,7 @@ use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadedModule, LoadingPro
 use roc_mono::ir::{generate_glue_procs, GlueProc, OptLevel};
 use roc_mono::layout::{GlobalLayoutInterner, LayoutCache, LayoutInterner};
 use roc_packaging::cache::{self, RocCacheDir};
-use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+use roc_reporting::report::RenderTarget;
 use roc_target::{Architecture, TargetInfo};
 use roc_types::subs::{Subs, Variable};
 use std::fs::File;
@@ -369,12 +369,9 @@ pub fn load_types(
         full_file_path,
         RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
         LoadConfig {
-            target_info,
-            function_kind,
             render: RenderTarget::Generic,
-            palette: DEFAULT_PALETTE,
             threading,
-            exec_mode: ExecutionMode::Check,
+            ..LoadConfig::new(target_info, function_kind, ExecutionMode::Check)
         },
     )
     .unwrap_or_else(|problem| match problem {
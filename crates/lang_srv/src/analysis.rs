@@ -4,30 +4,45 @@ use std::{
 };
 
 use bumpalo::Bump;
-use roc_can::{abilities::AbilitiesStore, expr::Declarations};
+use roc_can::{
+    abilities::AbilitiesStore,
+    expr::{DeclarationTag, Declarations},
+};
 use roc_collections::MutMap;
-use roc_load::{CheckedModule, LoadedModule};
+use roc_load::{
+    CheckedModule, ExecutionMode, LoadConfig, LoadResult, LoadStart, LoadedModule, Threading,
+};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_packaging::cache::{self, RocCacheDir};
-use roc_region::all::LineInfo;
+use roc_region::all::{LineInfo, Loc};
 use roc_reporting::report::RocDocAllocator;
 use roc_solve_problem::TypeError;
 use roc_types::subs::Subs;
 use tower_lsp::lsp_types::{
-    Diagnostic, GotoDefinitionResponse, Hover, HoverContents, Location, MarkedString, Position,
-    Range, SemanticTokenType, SemanticTokens, SemanticTokensResult, TextEdit, Url,
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionResponse, CompletionResponse,
+    Diagnostic, DocumentSymbolResponse, GotoDefinitionResponse, Hover, HoverContents, InlayHint,
+    InlayHintKind, InlayHintLabel, Location, MarkedString, Position, Range, SelectionRange,
+    SemanticTokenType, SemanticTokens, SemanticTokensResult, TextEdit, Url, WorkspaceEdit,
 };
 
 use crate::convert::{
     diag::{IntoLspDiagnostic, ProblemFmt},
-    ToRange, ToRocPosition,
+    ToRange, ToRegion, ToRocPosition,
 };
 
+mod completion;
+mod document_symbols;
 mod parse_ast;
 mod semantic_tokens;
 mod tokens;
 
-use self::{parse_ast::Ast, semantic_tokens::arrange_semantic_tokens, tokens::Token};
+use self::{
+    completion::completion_items,
+    document_symbols::{document_symbols, parsed_document_symbols},
+    parse_ast::Ast,
+    semantic_tokens::arrange_semantic_tokens,
+    tokens::Token,
+};
 pub const HIGHLIGHT_TOKENS_LEGEND: &[SemanticTokenType] = Token::LEGEND;
 
 pub(crate) struct GlobalAnalysis {
@@ -42,17 +57,28 @@ impl GlobalAnalysis {
         let src_dir = find_src_dir(&fi).to_path_buf();
         let line_info = LineInfo::new(&source);
 
-        let loaded = roc_load::load_and_typecheck_str(
-            &arena,
-            fi,
-            &source,
-            src_dir,
-            roc_target::TargetInfo::default_x86_64(),
-            roc_load::FunctionKind::LambdaSet,
-            roc_reporting::report::RenderTarget::Generic,
-            RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
-            roc_reporting::report::DEFAULT_PALETTE,
-        );
+        let cache_dir = cache::roc_cache_dir();
+        let roc_cache_dir = RocCacheDir::Persistent(cache_dir.as_path());
+        let load_config = LoadConfig {
+            render: roc_reporting::report::RenderTarget::Generic,
+            threading: Threading::Single,
+            // a def the user is halfway through typing shouldn't hide the rest of the module
+            recover_from_syntax_errors: true,
+            ..LoadConfig::new(
+                roc_target::TargetInfo::default_x86_64(),
+                roc_load::FunctionKind::LambdaSet,
+                ExecutionMode::Check,
+            )
+        };
+
+        let loaded = LoadStart::from_str(&arena, fi, &source, roc_cache_dir, src_dir)
+            .and_then(|load_start| {
+                roc_load::load_single_threaded(&arena, load_start, roc_cache_dir, load_config)
+            })
+            .map(|result| match result {
+                LoadResult::TypeChecked(module) => module,
+                LoadResult::HeadersLoaded(_) | LoadResult::Monomorphized(_) => unreachable!(""),
+            });
 
         let module = match loaded {
             Ok(module) => module,
@@ -68,6 +94,7 @@ impl GlobalAnalysis {
                     source,
                     module: None,
                     diagnostics: all_problems,
+                    missing_branches: vec![],
                 };
 
                 return GlobalAnalysis {
@@ -83,6 +110,9 @@ impl GlobalAnalysis {
             mut can_problems,
             mut type_problems,
             mut declarations_by_id,
+            mut occurrences,
+            mut bindings,
+            mut doc_comments,
             sources,
             mut typechecked,
             solved,
@@ -101,6 +131,9 @@ impl GlobalAnalysis {
             can_problems: &mut can_problems,
             type_problems: &mut type_problems,
             declarations_by_id: &mut declarations_by_id,
+            occurrences: &mut occurrences,
+            bindings: &mut bindings,
+            doc_comments: &mut doc_comments,
             typechecked: &mut typechecked,
             root_module: &mut root_module,
         };
@@ -159,6 +192,9 @@ struct AnalyzedDocumentBuilder<'a> {
     can_problems: &'a mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &'a mut MutMap<ModuleId, Vec<TypeError>>,
     declarations_by_id: &'a mut MutMap<ModuleId, Declarations>,
+    occurrences: &'a mut MutMap<ModuleId, Vec<Loc<Symbol>>>,
+    bindings: &'a mut MutMap<ModuleId, Vec<Loc<Symbol>>>,
+    doc_comments: &'a mut MutMap<ModuleId, MutMap<Symbol, String>>,
     typechecked: &'a mut MutMap<ModuleId, CheckedModule>,
     root_module: &'a mut Option<RootModule>,
 }
@@ -189,13 +225,24 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
             subs,
             abilities,
             declarations,
+            occurrences: self.occurrences.remove(&module_id).unwrap_or_default(),
+            bindings: self.bindings.remove(&module_id).unwrap_or_default(),
+            doc_comments: self.doc_comments.remove(&module_id).unwrap_or_default(),
             module_id,
             interns: self.interns.clone(),
             module_id_to_url: self.module_id_to_url.clone(),
         };
 
         let line_info = LineInfo::new(&source);
-        let diagnostics = self.build_diagnostics(&path, &source, &line_info, module_id);
+        let mut missing_branches = vec![];
+        let diagnostics = self.build_diagnostics(
+            &path,
+            &source,
+            &line_info,
+            module_id,
+            &analyzed_module.declarations,
+            &mut missing_branches,
+        );
 
         AnalyzedDocument {
             url: path_to_url(&path),
@@ -203,6 +250,7 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
             source: source.into(),
             module: Some(analyzed_module),
             diagnostics,
+            missing_branches,
         }
     }
 
@@ -212,6 +260,8 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
         source: &str,
         line_info: &LineInfo,
         module_id: ModuleId,
+        declarations: &Declarations,
+        missing_branches: &mut Vec<MissingBranches>,
     ) -> Vec<Diagnostic> {
         let lines: Vec<_> = source.lines().collect();
 
@@ -235,6 +285,11 @@ impl<'a> AnalyzedDocumentBuilder<'a> {
         }
 
         for type_problem in type_problems {
+            if let Some(fix) = MissingBranches::new(&alloc, line_info, declarations, &type_problem)
+            {
+                missing_branches.push(fix);
+            }
+
             if let Some(diag) = type_problem.into_lsp_diagnostic(&fmt) {
                 all_problems.push(diag);
             }
@@ -253,11 +308,52 @@ struct AnalyzedModule {
     subs: Subs,
     abilities: AbilitiesStore,
     declarations: Declarations,
+    /// Where each symbol is referred to, as recorded by canonicalization
+    occurrences: Vec<Loc<Symbol>>,
+    /// Where each symbol is bound, including local defs and arguments
+    bindings: Vec<Loc<Symbol>>,
+    /// The `##` doc comments of the module's top-level defs
+    doc_comments: MutMap<Symbol, String>,
     // We need this because ModuleIds are not stable between compilations, so a ModuleId visible to
     // one module may not be true global to the language server.
     module_id_to_url: ModuleIdToUrl,
 }
 
+/// Branches that would make a non-exhaustive `when` exhaustive, offered as a quick fix.
+#[derive(Debug)]
+struct MissingBranches {
+    /// The branches of the `when`
+    range: Range,
+    edit: TextEdit,
+}
+
+impl MissingBranches {
+    fn new(
+        alloc: &RocDocAllocator,
+        line_info: &LineInfo,
+        declarations: &Declarations,
+        problem: &TypeError,
+    ) -> Option<Self> {
+        let (region, patterns) = roc_reporting::report::missing_when_branches(alloc, problem)?;
+
+        // The region starts at the `when` condition, so line the new branches up with the
+        // existing ones instead.
+        let first_branch = roc_can::traverse::find_first_when_branch(region, declarations)?;
+        let indent = " ".repeat(first_branch.to_range(line_info).start.character as usize);
+
+        let range = region.to_range(line_info);
+        let new_text: String = patterns
+            .iter()
+            .map(|pattern| format!("\n{indent}{pattern} -> crash \"TODO\""))
+            .collect();
+
+        Some(MissingBranches {
+            range,
+            edit: TextEdit::new(Range::new(range.end, range.end), new_text),
+        })
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct AnalyzedDocument {
     url: Url,
@@ -265,9 +361,22 @@ pub(crate) struct AnalyzedDocument {
     source: String,
     module: Option<AnalyzedModule>,
     diagnostics: Vec<Diagnostic>,
+    missing_branches: Vec<MissingBranches>,
 }
 
 impl AnalyzedDocument {
+    /// A document that is parsed on demand, but never checked.
+    pub fn unchecked(url: Url, source: String) -> Self {
+        AnalyzedDocument {
+            url,
+            line_info: LineInfo::new(&source),
+            source,
+            module: None,
+            diagnostics: vec![],
+            missing_branches: vec![],
+        }
+    }
+
     pub fn url(&self) -> &Url {
         &self.url
     }
@@ -352,6 +461,112 @@ impl AnalyzedDocument {
         })
     }
 
+    /// The symbols in scope at `position`, with those whose type fits there ranked first.
+    pub fn completion(&mut self, position: Position) -> Option<CompletionResponse> {
+        let AnalyzedDocument {
+            line_info, module, ..
+        } = self;
+
+        let AnalyzedModule {
+            subs,
+            declarations,
+            module_id,
+            interns,
+            ..
+        } = module.as_mut()?;
+
+        let position = position.to_roc_position(line_info);
+        let items = completion_items(position, declarations, subs, *module_id, interns);
+
+        Some(CompletionResponse::Array(items))
+    }
+
+    /// Hints showing the inferred types of top-level defs that have no annotation.
+    pub fn inlay_hints(&mut self, range: Range) -> Option<Vec<InlayHint>> {
+        let AnalyzedDocument {
+            line_info, module, ..
+        } = self;
+
+        let AnalyzedModule {
+            subs,
+            declarations,
+            module_id,
+            interns,
+            ..
+        } = module.as_mut()?;
+
+        let mut hints = Vec::new();
+
+        for (index, tag) in declarations.declarations.iter().enumerate() {
+            match tag {
+                DeclarationTag::Value
+                | DeclarationTag::Function(_)
+                | DeclarationTag::Recursive(_)
+                | DeclarationTag::TailRecursive(_) => {}
+                _ => continue,
+            }
+
+            if declarations.annotations[index].is_some() {
+                continue;
+            }
+
+            let position = declarations.symbols[index].region.to_range(line_info).end;
+            if position < range.start || position > range.end {
+                continue;
+            }
+
+            let snapshot = subs.snapshot();
+            let type_str = roc_types::pretty_print::name_and_print_var(
+                declarations.variables[index],
+                subs,
+                *module_id,
+                interns,
+                roc_types::pretty_print::DebugPrint::NOTHING,
+            );
+            subs.rollback_to(snapshot);
+
+            hints.push(InlayHint {
+                position,
+                label: InlayHintLabel::String(format!(": {type_str}")),
+                kind: Some(InlayHintKind::TYPE),
+                text_edits: None,
+                tooltip: None,
+                padding_left: Some(true),
+                padding_right: None,
+                data: None,
+            });
+        }
+
+        Some(hints)
+    }
+
+    /// The chain of syntax nodes enclosing `position`, innermost first, for structural selection.
+    pub fn selection_range(&self, position: Position) -> SelectionRange {
+        let line_info = self.line_info();
+
+        let innermost = SelectionRange {
+            range: Range::new(position, position),
+            parent: None,
+        };
+
+        let Some(AnalyzedModule { declarations, .. }) = self.module() else {
+            return innermost;
+        };
+
+        let pos = position.to_roc_position(line_info);
+        let regions = roc_can::traverse::find_enclosing_regions_at(pos, declarations);
+
+        regions
+            .into_iter()
+            .fold(None, |parent, region| {
+                Some(SelectionRange {
+                    range: region.to_range(line_info),
+                    parent: parent.map(Box::new),
+                })
+            })
+            .unwrap_or(innermost)
+    }
+
     pub fn definition(&self, symbol: Symbol) -> Option<GotoDefinitionResponse> {
         let AnalyzedModule { declarations, .. } = self.module()?;
 
@@ -362,11 +577,58 @@ impl AnalyzedDocument {
         Some(GotoDefinitionResponse::Scalar(self.location(range)))
     }
 
+    /// The `##` doc comment above the top-level def of `symbol`, if it has one.
+    pub fn doc_comment(&self, symbol: Symbol) -> Option<String> {
+        self.module()?.doc_comments.get(&symbol).cloned()
+    }
+
+    pub fn document_symbols(&self) -> Option<DocumentSymbolResponse> {
+        let symbols = match self.module() {
+            Some(AnalyzedModule {
+                declarations,
+                interns,
+                ..
+            }) => document_symbols(declarations, interns, self.line_info()),
+            None => {
+                // This module was never checked, so outline what we can parse.
+                let arena = &Bump::new();
+                let ast = Ast::parse(arena, &self.source).ok()?;
+
+                parsed_document_symbols(ast.defs(), &self.source, self.line_info())
+            }
+        };
+
+        Some(DocumentSymbolResponse::Nested(symbols))
+    }
+
+    pub fn code_actions(&self, range: Range) -> Option<CodeActionResponse> {
+        let actions = self
+            .missing_branches
+            .iter()
+            .filter(|fix| fix.range.start <= range.end && range.start <= fix.range.end)
+            .map(|fix| {
+                let changes = HashMap::from([(self.url.clone(), vec![fix.edit.clone()])]);
+
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title: "Add missing branches".to_string(),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(changes),
+                        ..WorkspaceEdit::default()
+                    }),
+                    ..CodeAction::default()
+                })
+            })
+            .collect();
+
+        Some(actions)
+    }
+
     pub fn format(&self) -> Option<Vec<TextEdit>> {
         let source = &self.source;
         let arena = &Bump::new();
 
-        let ast = Ast::parse(arena, source).ok()?;
+        let ast = Ast::parse(arena, source).ok().filter(Ast::is_complete)?;
         let fmt = ast.fmt();
 
         if source == fmt.as_str() {
@@ -378,6 +640,24 @@ impl AnalyzedDocument {
         }
     }
 
+    pub fn format_range(&self, range: Range) -> Option<Vec<TextEdit>> {
+        let source = &self.source;
+        let arena = &Bump::new();
+        let line_info = self.line_info();
+
+        let ast = Ast::parse(arena, source).ok().filter(Ast::is_complete)?;
+        let (region, fmt) = ast.fmt_range(range.to_region(line_info))?;
+
+        let replaced = &source[region.start().offset as usize..region.end().offset as usize];
+
+        if replaced == fmt {
+            None
+        } else {
+            let text_edit = TextEdit::new(region.to_range(line_info), fmt.to_string());
+            Some(vec![text_edit])
+        }
+    }
+
     pub fn semantic_tokens(&self) -> Option<SemanticTokensResult> {
         let source = &self.source;
         let arena = &Bump::new();
@@ -393,7 +673,264 @@ impl AnalyzedDocument {
         }))
     }
 
+    /// Locations in this document where `symbol` is used, and where it is bound if
+    /// `include_declaration` is set.
+    pub fn references(&self, symbol: Symbol, include_declaration: bool) -> Vec<Location> {
+        let Some(AnalyzedModule {
+            occurrences,
+            bindings,
+            ..
+        }) = self.module()
+        else {
+            return vec![];
+        };
+
+        let bound = bindings.iter().filter(|_| include_declaration);
+
+        occurrences
+            .iter()
+            .chain(bound)
+            .filter(|loc| loc.value == symbol)
+            .map(|loc| self.location(loc.region.to_range(self.line_info())))
+            .collect()
+    }
+
     pub(crate) fn module_url(&self, module_id: ModuleId) -> Option<Url> {
         self.module()?.module_id_to_url.get(&module_id).cloned()
     }
+
+    pub(crate) fn symbol_name(&self, symbol: Symbol) -> Option<&str> {
+        Some(symbol.as_str(&self.module()?.interns))
+    }
+
+    /// The symbol this document's own compilation uses for `ident` in the module at `module_url`.
+    /// Neither ModuleIds nor IdentIds are stable between compilations, so symbols from other
+    /// documents have to be looked up by name.
+    pub(crate) fn resolve_symbol(&self, module_url: &Url, ident: &str) -> Option<Symbol> {
+        let AnalyzedModule {
+            interns,
+            module_id_to_url,
+            ..
+        } = self.module()?;
+
+        let module_id = module_id_to_url
+            .iter()
+            .find(|(_, url)| *url == module_url)
+            .map(|(module_id, _)| *module_id)?;
+        let ident_id = interns.all_ident_ids.get(&module_id)?.get_id(ident)?;
+
+        Some(Symbol::new(module_id, ident_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn analyze(source: &str) -> AnalyzedDocument {
+        let path = std::env::temp_dir().join("roc_lang_srv_analysis/Test.roc");
+        let url = Url::from_file_path(&path).unwrap();
+
+        GlobalAnalysis::new(url.clone(), source.to_string())
+            .documents
+            .into_iter()
+            .find(|document| document.url == url)
+            .unwrap()
+    }
+
+    fn apply_code_actions(document: &AnalyzedDocument) -> String {
+        let actions = document
+            .code_actions(document.whole_document_range())
+            .unwrap();
+        let mut source = document.source.clone();
+
+        // apply from the back, so the earlier offsets stay valid
+        let mut edits: Vec<TextEdit> = actions
+            .into_iter()
+            .flat_map(|action| match action {
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    edit: Some(edit), ..
+                }) => edit.changes.unwrap().into_values().flatten().collect(),
+                _ => vec![],
+            })
+            .collect();
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.range.start));
+
+        for edit in edits {
+            let line_info = document.line_info();
+            let start = edit.range.start.to_roc_position(line_info).offset as usize;
+            let end = edit.range.end.to_roc_position(line_info).offset as usize;
+
+            source.replace_range(start..end, &edit.new_text);
+        }
+
+        source
+    }
+
+    const SCOPES: &str = indoc::indoc!(
+        r#"
+        interface Test exposes [f] imports []
+
+        top = 1
+
+        f = \arg ->
+            local = arg + top
+            when local is
+                0 -> local
+                n -> n + top
+        "#
+    );
+
+    /// The position just before the first occurrence of `needle` in the document
+    fn position_of(document: &AnalyzedDocument, needle: &str) -> roc_region::all::Position {
+        let offset = document.source.find(needle).unwrap();
+
+        roc_region::all::Position::new(offset as u32)
+    }
+
+    fn region_str(document: &AnalyzedDocument, region: roc_region::all::Region) -> &str {
+        &document.source[region.start().offset as usize..region.end().offset as usize]
+    }
+
+    #[test]
+    fn symbols_in_scope_depend_on_when_branch() {
+        let document = analyze(SCOPES);
+        let module = document.module().unwrap();
+
+        let names_at = |needle| {
+            roc_can::traverse::find_symbols_in_scope_at(
+                position_of(&document, needle),
+                &module.declarations,
+            )
+            .into_iter()
+            .map(|(symbol, _)| symbol.as_str(&module.interns).to_string())
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names_at("n + top"), vec!["top", "f", "arg", "local", "n"]);
+        // the `local` in the `0` branch, where `n` is not bound
+        assert_eq!(names_at("local\n"), vec!["top", "f", "arg", "local"]);
+    }
+
+    #[test]
+    fn enclosing_regions_go_from_outermost_to_innermost() {
+        let document = analyze(SCOPES);
+        let module = document.module().unwrap();
+
+        let regions = roc_can::traverse::find_enclosing_regions_at(
+            position_of(&document, "n + top"),
+            &module.declarations,
+        );
+
+        assert!(region_str(&document, regions[0]).starts_with("f = "));
+        assert_eq!(region_str(&document, *regions.last().unwrap()), "n");
+        assert!(regions
+            .windows(2)
+            .all(|pair| pair[0] != pair[1] && pair[0].contains(&pair[1])));
+    }
+
+    #[test]
+    fn doc_comments_come_with_the_module() {
+        let document = analyze(indoc::indoc!(
+            r#"
+            interface Test exposes [documented, plain] imports []
+
+            ## Says hello
+            documented = "hello"
+
+            plain = "plain"
+            "#
+        ));
+
+        let doc_comment = |name: &str| {
+            let symbol = document.resolve_symbol(&document.url, name).unwrap();
+            document.doc_comment(symbol)
+        };
+
+        assert_eq!(doc_comment("documented").as_deref(), Some("Says hello\n"));
+        assert_eq!(doc_comment("plain"), None);
+    }
+
+    #[test]
+    fn references_include_bindings_on_request() {
+        let document = analyze(SCOPES);
+
+        let lines = |name: &str, include_declaration: bool| -> Vec<u32> {
+            let symbol = document.resolve_symbol(&document.url, name).unwrap();
+
+            document
+                .references(symbol, include_declaration)
+                .iter()
+                .map(|location| location.range.start.line)
+                .collect()
+        };
+
+        assert_eq!(lines("top", false), vec![5, 8]);
+        assert_eq!(lines("top", true), vec![5, 8, 2]);
+        // locals aren't in the module's declarations, but their bindings are still known
+        assert_eq!(lines("local", false), vec![6, 7]);
+        assert_eq!(lines("local", true), vec![6, 7, 5]);
+        assert!(document.resolve_symbol(&document.url, "nothing").is_none());
+    }
+
+    #[test]
+    fn syntax_errors_leave_the_rest_of_the_module_checked() {
+        let document = analyze(indoc::indoc!(
+            r#"
+            interface Test exposes [good] imports []
+
+            broken = [
+
+            good = "good"
+            "#
+        ));
+
+        assert!(document.resolve_symbol(&document.url, "good").is_some());
+        assert!(document
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.range.start.line == 2));
+        assert!(document.semantic_tokens().is_some());
+        // formatting would drop `broken`
+        assert!(document.format().is_none());
+    }
+
+    #[test]
+    fn missing_branches_line_up_with_nested_when() {
+        let document = analyze(indoc::indoc!(
+            r#"
+            interface Test exposes [f] imports []
+
+            f : [A, B, C], [X, Y] -> Str
+            f = \outer, inner ->
+                when outer is
+                    A ->
+                        when inner is
+                            X -> "ax"
+
+                    B -> "b"
+                    C -> "c"
+            "#
+        ));
+
+        assert_eq!(
+            apply_code_actions(&document),
+            indoc::indoc!(
+                r#"
+                interface Test exposes [f] imports []
+
+                f : [A, B, C], [X, Y] -> Str
+                f = \outer, inner ->
+                    when outer is
+                        A ->
+                            when inner is
+                                X -> "ax"
+                                Y -> crash "TODO"
+
+                        B -> "b"
+                        C -> "c"
+                "#
+            )
+        );
+    }
 }
@@ -0,0 +1,110 @@
+use roc_can::{expr::Declarations, traverse};
+use roc_checkmate::with_checkmate;
+use roc_module::symbol::{Interns, ModuleId};
+use roc_region::all::Position;
+use roc_solve_schema::UnificationMode;
+use roc_types::{
+    pretty_print::{name_and_print_var, DebugPrint},
+    subs::{Content, FlatType, Subs, Variable},
+    types::Polarity,
+};
+use roc_unify::{
+    unify::{unify, Unified},
+    Env,
+};
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind};
+
+/// How well a candidate's type fits the type expected where it would be inserted.
+/// Better fits sort first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TypeFit {
+    /// The candidate itself has the expected type
+    Exact,
+    /// The candidate is a function that returns the expected type
+    Returns,
+    Other,
+}
+
+/// The symbols in scope at `position`, ranked by how well their types fit the expression there:
+/// first those whose type unifies with it, then functions returning such a type, then the rest.
+pub fn completion_items(
+    position: Position,
+    declarations: &Declarations,
+    subs: &mut Subs,
+    module_id: ModuleId,
+    interns: &Interns,
+) -> Vec<CompletionItem> {
+    let expected = traverse::find_closest_type_at(position, declarations).map(|(_, var)| var);
+
+    traverse::find_symbols_in_scope_at(position, declarations)
+        .into_iter()
+        .map(|(symbol, var)| {
+            // checking the fit and naming type variables both change `subs`, so every candidate
+            // starts from the types as they were solved
+            let snapshot = subs.snapshot();
+
+            let fit = match expected {
+                Some(expected) => type_fit(subs, expected, var),
+                None => TypeFit::Other,
+            };
+
+            let kind = match function_return_var(subs, var) {
+                Some(_) => CompletionItemKind::FUNCTION,
+                None => CompletionItemKind::VARIABLE,
+            };
+
+            let type_str = name_and_print_var(var, subs, module_id, interns, DebugPrint::NOTHING);
+
+            subs.rollback_to(snapshot);
+
+            let label = symbol.as_str(interns).to_string();
+
+            CompletionItem {
+                sort_text: Some(format!("{}{}", fit as u8, label)),
+                label,
+                kind: Some(kind),
+                detail: Some(type_str),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn type_fit(subs: &mut Subs, expected: Variable, candidate: Variable) -> TypeFit {
+    if unifies(subs, expected, candidate) {
+        return TypeFit::Exact;
+    }
+
+    match function_return_var(subs, candidate) {
+        Some(ret) if unifies(subs, expected, ret) => TypeFit::Returns,
+        _ => TypeFit::Other,
+    }
+}
+
+/// Whether the two types would unify. Leaves `subs` as it was.
+fn unifies(subs: &mut Subs, var1: Variable, var2: Variable) -> bool {
+    let snapshot = subs.snapshot();
+
+    let unified = unify(
+        &mut with_checkmate!({
+            on => Env::new(subs, None),
+            off => Env::new(subs),
+        }),
+        var1,
+        var2,
+        UnificationMode::EQ,
+        Polarity::OF_VALUE,
+    );
+
+    subs.rollback_to(snapshot);
+
+    matches!(unified, Unified::Success { .. })
+}
+
+fn function_return_var(subs: &Subs, var: Variable) -> Option<Variable> {
+    match subs.get_content_without_compacting(var) {
+        Content::Structure(FlatType::Func(_, _, ret)) => Some(*ret),
+        Content::Alias(_, _, real_var, _) => function_return_var(subs, *real_var),
+        _ => None,
+    }
+}
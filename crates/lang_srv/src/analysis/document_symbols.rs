@@ -0,0 +1,185 @@
+use roc_can::{
+    def::Def,
+    expr::{Declarations, Expr},
+    pattern::Pattern,
+    traverse::{walk_decl, walk_def, DeclarationInfo, Visitor},
+};
+use roc_module::symbol::{Interns, Symbol};
+use roc_parse::ast::{Defs, Pattern as ParsedPattern, TypeDef, ValueDef};
+use roc_region::all::{LineInfo, Loc, Region};
+use tower_lsp::lsp_types::{DocumentSymbol, SymbolKind};
+
+use crate::convert::ToRange;
+
+/// Collects the defs of a module, with each def's nested defs as its children, so editors can
+/// show an outline and a breadcrumb of where the cursor is.
+pub fn document_symbols(
+    declarations: &Declarations,
+    interns: &Interns,
+    line_info: &LineInfo,
+) -> Vec<DocumentSymbol> {
+    let mut collector = Collector {
+        interns,
+        line_info,
+        levels: vec![vec![]],
+    };
+    collector.visit_decls(declarations);
+
+    collector.levels.pop().unwrap_or_default()
+}
+
+/// Like [document_symbols], but for a module that was only parsed, never checked. Only top-level
+/// defs are listed, with their annotations (if any) as detail.
+pub fn parsed_document_symbols(
+    defs: &Defs<'_>,
+    source: &str,
+    line_info: &LineInfo,
+) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+
+    for (def, region) in defs.defs().zip(defs.regions.iter()) {
+        let (name, name_region, kind, annotation) = match def {
+            Ok(TypeDef::Alias { header, .. }) | Ok(TypeDef::Opaque { header, .. }) => (
+                header.name.value,
+                header.name.region,
+                SymbolKind::STRUCT,
+                None,
+            ),
+            Ok(TypeDef::Ability { header, .. }) => (
+                header.name.value,
+                header.name.region,
+                SymbolKind::INTERFACE,
+                None,
+            ),
+            Err(ValueDef::Annotation(pattern, annotation)) => {
+                match parsed_pattern_name(&pattern.value) {
+                    Some(name) => (name, pattern.region, SymbolKind::CONSTANT, Some(annotation)),
+                    None => continue,
+                }
+            }
+            Err(ValueDef::AnnotatedBody {
+                ann_pattern,
+                ann_type,
+                ..
+            }) => match parsed_pattern_name(&ann_pattern.value) {
+                Some(name) => (
+                    name,
+                    ann_pattern.region,
+                    SymbolKind::CONSTANT,
+                    Some(*ann_type),
+                ),
+                None => continue,
+            },
+            Err(ValueDef::Body(pattern, _)) => match parsed_pattern_name(&pattern.value) {
+                Some(name) => (name, pattern.region, SymbolKind::CONSTANT, None),
+                None => continue,
+            },
+            Err(ValueDef::Dbg { .. } | ValueDef::Expect { .. } | ValueDef::ExpectFx { .. }) => {
+                continue
+            }
+        };
+
+        #[allow(deprecated)] // `deprecated` is a required field, even though it is deprecated
+        symbols.push(DocumentSymbol {
+            name: name.to_string(),
+            detail: annotation.map(|annotation| source[annotation.byte_range()].to_string()),
+            kind,
+            tags: None,
+            deprecated: None,
+            range: region.to_range(line_info),
+            selection_range: name_region.to_range(line_info),
+            children: None,
+        });
+    }
+
+    symbols
+}
+
+fn parsed_pattern_name<'a>(pattern: &ParsedPattern<'a>) -> Option<&'a str> {
+    match pattern {
+        ParsedPattern::Identifier(name) => Some(*name),
+        ParsedPattern::SpaceBefore(pattern, _) | ParsedPattern::SpaceAfter(pattern, _) => {
+            parsed_pattern_name(pattern)
+        }
+        _ => None,
+    }
+}
+
+struct Collector<'a> {
+    interns: &'a Interns,
+    line_info: &'a LineInfo,
+    /// The symbols found so far at each level of nesting; the last level is the innermost.
+    levels: Vec<Vec<DocumentSymbol>>,
+}
+
+impl Collector<'_> {
+    fn with_children(
+        &mut self,
+        loc_symbol: Loc<Symbol>,
+        kind: SymbolKind,
+        region: Region,
+        walk: impl FnOnce(&mut Self),
+    ) {
+        self.levels.push(vec![]);
+        walk(self);
+        let children = self.levels.pop().unwrap_or_default();
+
+        #[allow(deprecated)] // `deprecated` is a required field, even though it is deprecated
+        let symbol = DocumentSymbol {
+            name: loc_symbol.value.as_str(self.interns).to_string(),
+            detail: None,
+            kind,
+            tags: None,
+            deprecated: None,
+            range: region.to_range(self.line_info),
+            selection_range: loc_symbol.region.to_range(self.line_info),
+            children: if children.is_empty() {
+                None
+            } else {
+                Some(children)
+            },
+        };
+
+        if let Some(level) = self.levels.last_mut() {
+            level.push(symbol);
+        }
+    }
+}
+
+impl Visitor for Collector<'_> {
+    fn visit_decl(&mut self, decl: DeclarationInfo<'_>) {
+        let (loc_symbol, kind) = match &decl {
+            DeclarationInfo::Value { loc_symbol, .. } => (*loc_symbol, SymbolKind::CONSTANT),
+            DeclarationInfo::Function { loc_symbol, .. } => (*loc_symbol, SymbolKind::FUNCTION),
+            DeclarationInfo::Expectation { .. } | DeclarationInfo::Destructure { .. } => {
+                walk_decl(self, decl);
+                return;
+            }
+        };
+
+        let region = decl.region();
+        self.with_children(loc_symbol, kind, region, |collector| {
+            walk_decl(collector, decl)
+        });
+    }
+
+    fn visit_def(&mut self, def: &Def) {
+        let symbol = match def.loc_pattern.value {
+            Pattern::Identifier(symbol) => symbol,
+            _ => {
+                walk_def(self, def);
+                return;
+            }
+        };
+
+        let kind = match def.loc_expr.value {
+            Expr::Closure(_) => SymbolKind::FUNCTION,
+            _ => SymbolKind::VARIABLE,
+        };
+
+        let loc_symbol = Loc::at(def.loc_pattern.region, symbol);
+        self.with_children(loc_symbol, kind, def.region(), |collector| {
+            walk_def(collector, def)
+        });
+    }
+}
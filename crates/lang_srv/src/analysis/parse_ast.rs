@@ -1,10 +1,7 @@
 use bumpalo::Bump;
 use roc_fmt::Buf;
-use roc_parse::{
-    ast::{Defs, Module},
-    parser::SyntaxError,
-};
-use roc_region::all::Loc;
+use roc_parse::{ast::Defs, parser::SyntaxError};
+use roc_region::all::{Loc, Region};
 
 use self::format::FormattedAst;
 
@@ -14,45 +11,49 @@ mod format;
 
 pub struct Ast<'a> {
     arena: &'a Bump,
-    module: Module<'a>,
-    defs: Defs<'a>,
+    ast: roc_fmt::Ast<'a>,
+    /// Whether every def parsed; the ones that didn't are missing from `ast`
+    complete: bool,
 }
 
 impl<'a> Ast<'a> {
+    /// Parse as much of the source as possible, so a half-typed def doesn't hide the rest of
+    /// the module. Only a broken header is an error.
     pub fn parse(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
-        use roc_parse::{
-            module::{module_defs, parse_header},
-            parser::Parser,
-            state::State,
-        };
-
-        let (module, state) = parse_header(arena, State::new(src.as_bytes()))
-            .map_err(|e| SyntaxError::Header(e.problem))?;
-
-        let (_, defs, _) = module_defs().parse(arena, state, 0).map_err(|(_, e)| e)?;
+        let (ast, problems) = roc_fmt::Ast::parse_with_recovery(arena, src)?;
 
         Ok(Ast {
-            module,
-            defs,
             arena,
+            ast,
+            complete: problems.is_empty(),
         })
     }
 
-    pub fn fmt(&self) -> FormattedAst<'a> {
-        let mut buf = Buf::new_in(self.arena);
+    /// Formatting an incomplete AST would delete the defs that didn't parse.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
 
-        roc_fmt::module::fmt_module(&mut buf, &self.module);
+    pub fn defs(&self) -> &Defs<'a> {
+        &self.ast.defs
+    }
 
-        roc_fmt::def::fmt_defs(&mut buf, &self.defs, 0);
+    pub fn fmt(&self) -> FormattedAst<'a> {
+        let mut buf = Buf::new_in(self.arena);
 
-        buf.fmt_end_of_file();
+        roc_fmt::format_module(&mut buf, &self.ast);
 
         FormattedAst::new(buf)
     }
 
+    /// The region of the source to replace, and its replacement, to format the defs in `range`.
+    pub fn fmt_range(&self, range: Region) -> Option<(Region, &'a str)> {
+        roc_fmt::format_range(self.arena, &self.ast, range)
+    }
+
     pub fn semantic_tokens(&self) -> impl IntoIterator<Item = Loc<Token>> + '_ {
-        let header_tokens = self.module.iter_tokens(self.arena);
-        let body_tokens = self.defs.iter_tokens(self.arena);
+        let header_tokens = self.ast.module.iter_tokens(self.arena);
+        let body_tokens = self.ast.defs.iter_tokens(self.arena);
 
         header_tokens.into_iter().chain(body_tokens)
     }
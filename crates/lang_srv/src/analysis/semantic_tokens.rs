@@ -22,9 +22,15 @@ pub fn arrange_semantic_tokens(
         value: token,
     } in tokens
     {
-        let length = region.len();
+        // LSP counts columns and lengths in UTF-16 code units
+        let LineColumn { line, column } = line_info.convert_pos_utf16(region.start());
+        let end = line_info.convert_pos_utf16(region.end());
 
-        let LineColumn { line, column } = line_info.convert_pos(region.start());
+        let length = if end.line == line {
+            end.column - column
+        } else {
+            region.len()
+        };
 
         let delta_line = line - last_line;
         let delta_start = if delta_line == 0 {
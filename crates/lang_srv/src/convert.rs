@@ -11,7 +11,7 @@ impl ToRange for Region {
     type Feed = LineInfo;
 
     fn to_range(&self, line_info: &LineInfo) -> Range {
-        let LineColumnRegion { start, end } = line_info.convert_region(*self);
+        let LineColumnRegion { start, end } = line_info.convert_region_utf16(*self);
         Range {
             start: Position {
                 line: start.line,
@@ -42,11 +42,11 @@ impl ToRegion for Range {
             },
             end: LineColumn {
                 line: self.end.line,
-                column: self.end.line,
+                column: self.end.character,
             },
         };
 
-        line_info.convert_line_column_region(lc_region)
+        line_info.convert_line_column_region_utf16(lc_region)
     }
 }
 
@@ -64,7 +64,7 @@ impl ToRocPosition for tower_lsp::lsp_types::Position {
             line: self.line,
             column: self.character,
         };
-        line_info.convert_line_column(lc)
+        line_info.convert_line_column_utf16(lc)
     }
 }
 
@@ -137,14 +137,80 @@ pub(crate) mod diag {
                 LoadingProblem::ErrJoiningWorkerThreads => {
                     "Internal error: analysis worker threads died".to_string()
                 }
-                LoadingProblem::TriedToImportAppModule => {
-                    "Attempted to import app module".to_string()
+                LoadingProblem::FilenameCaseMismatch { expected, found } => {
+                    format!(
+                        "Expected {} but found {}, which is spelled with different casing",
+                        expected.display(),
+                        found.display()
+                    )
+                }
+                LoadingProblem::TriedToImportAppModule {
+                    filename,
+                    module_name,
+                    ..
+                } => {
+                    format!(
+                        "Module {} is imported, but {} is an app module",
+                        module_name.as_inner().as_str(),
+                        filename.display()
+                    )
                 }
                 LoadingProblem::FormattedReport(report) => report.clone(),
                 LoadingProblem::ImportCycle(_, _) => {
                     "Circular dependency between modules".to_string()
                 }
                 LoadingProblem::IncorrectModuleName(_) => "Incorrect module name".to_string(),
+                LoadingProblem::FileTooLarge {
+                    filename,
+                    size,
+                    limit,
+                } => {
+                    format!(
+                        "{} is {} bytes, which is over the {} byte limit for Roc source files",
+                        filename.display(),
+                        size,
+                        limit
+                    )
+                }
+                LoadingProblem::InvalidUtf8 {
+                    filename,
+                    line,
+                    column,
+                    ..
+                } => {
+                    format!(
+                        "{} is not valid UTF-8 (first invalid byte at line {}, column {})",
+                        filename.display(),
+                        line,
+                        column
+                    )
+                }
+                LoadingProblem::AmbiguousModule {
+                    module_name,
+                    candidates,
+                } => {
+                    format!(
+                        "Module {} is provided by more than one source root: {}",
+                        module_name,
+                        candidates
+                            .iter()
+                            .map(|path| path.display().to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                }
+                LoadingProblem::ModuleNotFound {
+                    module_name,
+                    suggestions,
+                    ..
+                } => match suggestions.first() {
+                    Some(suggestion) => format!(
+                        "Could not find module {}. Did you mean {}?",
+                        module_name,
+                        suggestion.display()
+                    ),
+                    None => format!("Could not find module {}", module_name),
+                },
                 LoadingProblem::CouldNotFindCacheDir => {
                     format!(
                         "Could not find Roc cache directory {}",
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use tower_lsp::lsp_types::{
-    Diagnostic, GotoDefinitionResponse, Hover, Position, SemanticTokensResult, TextEdit, Url,
+    CodeActionResponse, CompletionResponse, Diagnostic, DocumentSymbolResponse,
+    GotoDefinitionResponse, Hover, HoverContents, InlayHint, Location, MarkedString, Position,
+    Range, SelectionRange, SemanticTokensResult, TextEdit, Url,
 };
 
 use crate::analysis::{AnalyzedDocument, GlobalAnalysis};
@@ -19,6 +21,14 @@ pub(crate) struct Registry {
 impl Registry {
     pub fn apply_change(&mut self, change: DocumentChange) {
         match change {
+            DocumentChange::Modified(url, source) if is_cached_package_module(&url) => {
+                // Modules of downloaded packages can't be edited, so instead of compiling the
+                // whole package, only parse them as needed (e.g. for an outline). If the module
+                // was already checked as a dependency of another document, keep that analysis.
+                self.documents
+                    .entry(url.clone())
+                    .or_insert_with(|| AnalyzedDocument::unchecked(url, source));
+            }
             DocumentChange::Modified(url, source) => {
                 let GlobalAnalysis { documents } = GlobalAnalysis::new(url, source);
 
@@ -48,7 +58,28 @@ impl Registry {
     }
 
     pub fn hover(&mut self, url: &Url, position: Position) -> Option<Hover> {
-        self.document_by_url(url)?.hover(position)
+        let document = self.document_by_url(url)?;
+        let mut hover = document.hover(position)?;
+
+        let def_document = document.symbol_at(position).and_then(|symbol| {
+            let def_document_url = document.module_url(symbol.module_id())?;
+            let ident = document.symbol_name(symbol)?.to_string();
+            Some((def_document_url, ident))
+        });
+
+        // The defining document may have been compiled separately, so look the symbol up by name
+        let docs = def_document.and_then(|(def_document_url, ident)| {
+            let def_document = self.document_by_url(&def_document_url)?;
+            let symbol = def_document.resolve_symbol(&def_document_url, &ident)?;
+            def_document.doc_comment(symbol)
+        });
+
+        if let (Some(docs), HoverContents::Scalar(type_str)) = (docs, &hover.contents) {
+            hover.contents =
+                HoverContents::Array(vec![type_str.clone(), MarkedString::String(docs)]);
+        }
+
+        Some(hover)
     }
 
     pub fn goto_definition(
@@ -63,13 +94,83 @@ impl Registry {
         def_document.definition(symbol)
     }
 
+    pub fn references(
+        &mut self,
+        url: &Url,
+        position: Position,
+        include_declaration: bool,
+    ) -> Option<Vec<Location>> {
+        let document = self.document_by_url(url)?;
+        let symbol = document.symbol_at(position)?;
+        let def_document_url = document.module_url(symbol.module_id())?;
+        let ident = document.symbol_name(symbol)?.to_string();
+
+        let mut locations = Vec::new();
+
+        for other in self.documents.values() {
+            // Locals can share a name, so only the document asked about uses the symbol as is.
+            // Other documents were compiled separately and look it up by name.
+            let symbol = if other.url() == url {
+                Some(symbol)
+            } else {
+                other.resolve_symbol(&def_document_url, &ident)
+            };
+
+            if let Some(symbol) = symbol {
+                locations.extend(other.references(symbol, include_declaration));
+            }
+        }
+
+        Some(locations)
+    }
+
+    pub fn document_symbols(&mut self, url: &Url) -> Option<DocumentSymbolResponse> {
+        self.document_by_url(url)?.document_symbols()
+    }
+
+    pub fn completion(&mut self, url: &Url, position: Position) -> Option<CompletionResponse> {
+        self.document_by_url(url)?.completion(position)
+    }
+
+    pub fn code_actions(&mut self, url: &Url, range: Range) -> Option<CodeActionResponse> {
+        self.document_by_url(url)?.code_actions(range)
+    }
+
     pub fn formatting(&mut self, url: &Url) -> Option<Vec<TextEdit>> {
         let document = self.document_by_url(url)?;
         document.format()
     }
 
+    pub fn range_formatting(&mut self, url: &Url, range: Range) -> Option<Vec<TextEdit>> {
+        self.document_by_url(url)?.format_range(range)
+    }
+
+    pub fn inlay_hints(&mut self, url: &Url, range: Range) -> Option<Vec<InlayHint>> {
+        self.document_by_url(url)?.inlay_hints(range)
+    }
+
+    pub fn selection_ranges(
+        &mut self,
+        url: &Url,
+        positions: &[Position],
+    ) -> Option<Vec<SelectionRange>> {
+        let document = self.document_by_url(url)?;
+        let ranges = positions
+            .iter()
+            .map(|position| document.selection_range(*position))
+            .collect();
+        Some(ranges)
+    }
+
     pub fn semantic_tokens(&mut self, url: &Url) -> Option<SemanticTokensResult> {
         let document = self.document_by_url(url)?;
         document.semantic_tokens()
     }
 }
+
+fn is_cached_package_module(url: &Url) -> bool {
+    match url.to_file_path() {
+        Ok(path) => path.starts_with(roc_packaging::cache::roc_cache_dir()),
+        Err(()) => false,
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use analysis::HIGHLIGHT_TOKENS_LEGEND;
 use parking_lot::{Mutex, MutexGuard};
 use registry::{DocumentChange, Registry};
@@ -9,10 +12,52 @@ mod analysis;
 mod convert;
 mod registry;
 
+/// How long to wait after an edit before checking the document, so that a burst of
+/// keystrokes only triggers one check.
+const CHANGE_DEBOUNCE: Duration = Duration::from_millis(150);
+
 #[derive(Debug)]
 struct RocLs {
     client: Client,
     registry: Mutex<Registry>,
+    latest_versions: LatestVersions,
+}
+
+/// The most recent version of each document we've been told about.
+#[derive(Debug, Default)]
+struct LatestVersions(Mutex<HashMap<Url, i32>>);
+
+impl LatestVersions {
+    /// Notes that `version` of the document exists. Versions only go up, even if the
+    /// notifications for them are handled out of order.
+    fn record(&self, fi: &Url, version: i32) {
+        self.0
+            .lock()
+            .entry(fi.clone())
+            .and_modify(|latest| *latest = version.max(*latest))
+            .or_insert(version);
+    }
+
+    fn is_superseded(&self, fi: &Url, version: i32) -> bool {
+        match self.0.lock().get(fi) {
+            Some(latest) => *latest > version,
+            None => false,
+        }
+    }
+
+    /// Records `version`, then waits for the debounce period. Returns whether `version` is still
+    /// the latest, i.e. whether it is worth checking.
+    async fn settle(&self, fi: &Url, version: i32) -> bool {
+        self.record(fi, version);
+
+        tokio::time::sleep(CHANGE_DEBOUNCE).await;
+
+        !self.is_superseded(fi, version)
+    }
+
+    fn forget(&self, fi: &Url) {
+        self.0.lock().remove(fi);
+    }
 }
 
 impl std::panic::RefUnwindSafe for RocLs {}
@@ -22,6 +67,7 @@ impl RocLs {
         Self {
             client,
             registry: Mutex::new(Registry::default()),
+            latest_versions: LatestVersions::default(),
         }
     }
 
@@ -49,6 +95,11 @@ impl RocLs {
                 work_done_progress: None,
             },
         };
+        let document_range_formatting_provider = DocumentRangeFormattingOptions {
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        };
         let semantic_tokens_provider =
             SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
                 work_done_progress_options: WorkDoneProgressOptions {
@@ -66,14 +117,33 @@ impl RocLs {
             text_document_sync: Some(text_document_sync),
             hover_provider: Some(hover_provider),
             definition_provider: Some(OneOf::Right(definition_provider)),
+            references_provider: Some(OneOf::Left(true)),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             document_formatting_provider: Some(OneOf::Right(document_formatting_provider)),
+            document_range_formatting_provider: Some(OneOf::Right(
+                document_range_formatting_provider,
+            )),
             semantic_tokens_provider: Some(semantic_tokens_provider),
+            selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            completion_provider: Some(CompletionOptions::default()),
             ..ServerCapabilities::default()
         }
     }
 
+    /// Records a document content change, once the user has stopped typing for a moment.
+    /// Changes superseded by a later version of the same document are dropped.
+    async fn debounced_change(&self, fi: Url, text: String, version: i32) {
+        if self.latest_versions.settle(&fi, version).await {
+            self.change(fi, text, version).await;
+        }
+    }
+
     /// Records a document content change.
     async fn change(&self, fi: Url, text: String, version: i32) {
+        self.latest_versions.record(&fi, version);
+
         self.registry()
             .apply_change(DocumentChange::Modified(fi.clone(), text));
 
@@ -82,12 +152,18 @@ impl RocLs {
             Err(_) => return,
         };
 
+        // A newer version came in while we were checking this one; its diagnostics will follow.
+        if self.latest_versions.is_superseded(&fi, version) {
+            return;
+        }
+
         self.client
             .publish_diagnostics(fi, diagnostics, Some(version))
             .await;
     }
 
     async fn close(&self, fi: Url) {
+        self.latest_versions.forget(&fi);
         self.registry().apply_change(DocumentChange::Closed(fi));
     }
 }
@@ -122,7 +198,7 @@ impl LanguageServer for RocLs {
         let TextDocumentContentChangeEvent { text, .. } =
             params.content_changes.into_iter().next().unwrap();
 
-        self.change(uri, text, version).await;
+        self.debounced_change(uri, text, version).await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
@@ -167,6 +243,66 @@ impl LanguageServer for RocLs {
         })
     }
 
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let ReferenceParams {
+            text_document_position:
+                TextDocumentPositionParams {
+                    text_document,
+                    position,
+                },
+            context: ReferenceContext {
+                include_declaration,
+            },
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        panic_wrapper(|| {
+            self.registry()
+                .references(&text_document.uri, position, include_declaration)
+        })
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let DocumentSymbolParams {
+            text_document,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        panic_wrapper(|| self.registry().document_symbols(&text_document.uri))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let CompletionParams {
+            text_document_position:
+                TextDocumentPositionParams {
+                    text_document,
+                    position,
+                },
+            work_done_progress_params: _,
+            partial_result_params: _,
+            context: _,
+        } = params;
+
+        panic_wrapper(|| self.registry().completion(&text_document.uri, position))
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let CodeActionParams {
+            text_document,
+            range,
+            context: _,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        panic_wrapper(|| self.registry().code_actions(&text_document.uri, range))
+    }
+
     async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
         let DocumentFormattingParams {
             text_document,
@@ -177,6 +313,20 @@ impl LanguageServer for RocLs {
         panic_wrapper(|| self.registry().formatting(&text_document.uri))
     }
 
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let DocumentRangeFormattingParams {
+            text_document,
+            range,
+            options: _,
+            work_done_progress_params: _,
+        } = params;
+
+        panic_wrapper(|| self.registry().range_formatting(&text_document.uri, range))
+    }
+
     async fn semantic_tokens_full(
         &self,
         params: SemanticTokensParams,
@@ -189,6 +339,33 @@ impl LanguageServer for RocLs {
 
         panic_wrapper(|| self.registry().semantic_tokens(&text_document.uri))
     }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        let InlayHintParams {
+            text_document,
+            range,
+            work_done_progress_params: _,
+        } = params;
+
+        panic_wrapper(|| self.registry().inlay_hints(&text_document.uri, range))
+    }
+
+    async fn selection_range(
+        &self,
+        params: SelectionRangeParams,
+    ) -> Result<Option<Vec<SelectionRange>>> {
+        let SelectionRangeParams {
+            text_document,
+            positions,
+            work_done_progress_params: _,
+            partial_result_params: _,
+        } = params;
+
+        panic_wrapper(|| {
+            self.registry()
+                .selection_ranges(&text_document.uri, &positions)
+        })
+    }
 }
 
 fn panic_wrapper<T>(f: impl FnOnce() -> Option<T> + std::panic::UnwindSafe) -> Result<Option<T>> {
@@ -206,3 +383,41 @@ async fn main() {
     let (service, socket) = LspService::new(RocLs::new);
     Server::new(stdin, stdout, socket).serve(service).await;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn url() -> Url {
+        Url::parse("file:///tmp/Test.roc").unwrap()
+    }
+
+    #[test]
+    fn versions_only_go_up() {
+        let versions = LatestVersions::default();
+
+        versions.record(&url(), 2);
+        versions.record(&url(), 1);
+
+        assert!(versions.is_superseded(&url(), 1));
+        assert!(!versions.is_superseded(&url(), 2));
+    }
+
+    #[tokio::test]
+    async fn only_the_last_of_a_burst_of_changes_settles() {
+        let versions = LatestVersions::default();
+
+        let (first, second) = tokio::join!(versions.settle(&url(), 1), versions.settle(&url(), 2));
+
+        assert!(!first);
+        assert!(second);
+    }
+
+    #[tokio::test]
+    async fn changes_far_enough_apart_both_settle() {
+        let versions = LatestVersions::default();
+
+        assert!(versions.settle(&url(), 1).await);
+        assert!(versions.settle(&url(), 2).await);
+    }
+}
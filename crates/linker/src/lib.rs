@@ -6,10 +6,10 @@
 use memmap2::{Mmap, MmapMut};
 use object::Object;
 use roc_error_macros::internal_error;
-use roc_load::{EntryPoint, ExecutionMode, ExposedToHost, LoadConfig, Threading};
+use roc_load::{EntryPoint, ExecutionMode, ExposedToHost, LoadConfig};
 use roc_module::symbol::Interns;
 use roc_packaging::cache::RocCacheDir;
-use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+use roc_reporting::report::RenderTarget;
 use roc_solve::FunctionKind;
 use roc_target::get_target_triple_str;
 use std::cmp::Ordering;
@@ -102,12 +102,8 @@ pub fn generate_stub_lib(
         input_path.to_path_buf(),
         roc_cache_dir,
         LoadConfig {
-            target_info,
-            function_kind,
             render: RenderTarget::Generic,
-            palette: DEFAULT_PALETTE,
-            threading: Threading::AllAvailable,
-            exec_mode: ExecutionMode::Executable,
+            ..LoadConfig::new(target_info, function_kind, ExecutionMode::Executable)
         },
     )
     .unwrap_or_else(|problem| todo!("{:?}", problem));
@@ -62,12 +62,13 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
         src_dir,
         RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
         LoadConfig {
-            target_info,
-            function_kind: FunctionKind::LambdaSet,
-            render: roc_reporting::report::RenderTarget::ColorTerminal,
             palette,
             threading: Threading::Single,
-            exec_mode: ExecutionMode::Executable,
+            ..LoadConfig::new(
+                target_info,
+                FunctionKind::LambdaSet,
+                ExecutionMode::Executable,
+            )
         },
     );
 
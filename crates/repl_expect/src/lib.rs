@@ -97,7 +97,7 @@ mod test {
     use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult, run_roc_dylib};
     use roc_load::{ExecutionMode, FunctionKind, LoadConfig, LoadMonomorphizedError, Threading};
     use roc_packaging::cache::RocCacheDir;
-    use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
+    use roc_reporting::report::RenderTarget;
     use target_lexicon::Triple;
 
     use crate::run::expect_mono_module_to_dylib;
@@ -122,12 +122,8 @@ mod test {
         std::fs::write(&filename, source).unwrap();
 
         let load_config = LoadConfig {
-            target_info,
-            function_kind,
-            render: RenderTarget::ColorTerminal,
-            palette: DEFAULT_PALETTE,
             threading: Threading::Single,
-            exec_mode: ExecutionMode::Test,
+            ..LoadConfig::new(target_info, function_kind, ExecutionMode::Test)
         };
         let loaded = match roc_load::load_and_monomorphize_from_str(
             arena,
@@ -5,6 +5,8 @@ use roc_module::symbol::{Interns, ModuleId};
 use roc_region::all::LineInfo;
 use roc_solve_problem::TypeError;
 
+use crate::report::{Report, RocDocAllocator};
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Problems {
     pub fatally_errored: bool,
@@ -51,13 +53,16 @@ impl Problems {
     }
 }
 
+/// Print the errors, or the warnings if there are no errors. `import_chain` gives the modules
+/// through which a module was imported, nearest importer first, and is empty for the root module.
 pub fn report_problems(
     sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
     interns: &Interns,
+    import_chain: impl Fn(ModuleId) -> Vec<ModuleId>,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
 ) -> Problems {
-    use crate::report::{can_problem, type_problem, Report, RocDocAllocator, DEFAULT_PALETTE};
+    use crate::report::{can_problem, type_problem, DEFAULT_PALETTE};
     use roc_problem::Severity::*;
 
     let palette = DEFAULT_PALETTE;
@@ -87,10 +92,14 @@ pub fn report_problems(
         // Report parsing and canonicalization problems
         let alloc = RocDocAllocator::new(&src_lines, *home, interns);
 
+        // Problems in a module the user may never have opened say how it got imported
+        let chain = import_chain(*home);
+
         let problems = can_problems.remove(home).unwrap_or_default();
 
         for problem in problems.into_iter() {
             let report = can_problem(&alloc, &lines, module_path.clone(), problem);
+            let report = with_import_chain(&alloc, report, *home, &chain);
             let severity = report.severity;
             let mut buf = String::new();
 
@@ -114,6 +123,7 @@ pub fn report_problems(
 
         for problem in problems {
             if let Some(report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
+                let report = with_import_chain(&alloc, report, *home, &chain);
                 let severity = report.severity;
                 let mut buf = String::new();
 
@@ -171,3 +181,35 @@ pub fn report_problems(
         warnings: warnings.len(),
     }
 }
+
+fn with_import_chain<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    report: Report<'b>,
+    home: ModuleId,
+    chain: &[ModuleId],
+) -> Report<'b> {
+    use ven_pretty::DocAllocator;
+
+    if chain.is_empty() {
+        return report;
+    }
+
+    let mut note = vec![
+        alloc.note(""),
+        alloc.module(home),
+        alloc.reflow(" is imported by "),
+        alloc.module(chain[0]),
+    ];
+
+    for importer in &chain[1..] {
+        note.push(alloc.reflow(", which is imported by "));
+        note.push(alloc.module(*importer));
+    }
+
+    note.push(alloc.text("."));
+
+    Report {
+        doc: alloc.stack([report.doc, alloc.concat(note)]),
+        ..report
+    }
+}
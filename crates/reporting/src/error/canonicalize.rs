@@ -1,10 +1,10 @@
 use roc_collections::all::MutSet;
 use roc_module::ident::{Ident, Lowercase, ModuleName};
-use roc_module::symbol::DERIVABLE_ABILITIES;
+use roc_module::symbol::{Symbol, DERIVABLE_ABILITIES};
 use roc_problem::can::PrecedenceProblem::BothNonAssociative;
 use roc_problem::can::{
-    BadPattern, CycleEntry, ExtensionTypeKind, FloatErrorKind, IntErrorKind, Problem, RuntimeError,
-    ShadowKind,
+    BadPattern, CycleEntry, ExtensionTypeKind, FloatErrorKind, ImportCandidate, IntErrorKind,
+    Problem, RuntimeError, ShadowKind,
 };
 use roc_problem::Severity;
 use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Loc, Region};
@@ -20,6 +20,8 @@ const NAMING_PROBLEM: &str = "NAMING PROBLEM";
 const UNRECOGNIZED_NAME: &str = "UNRECOGNIZED NAME";
 const UNUSED_DEF: &str = "UNUSED DEFINITION";
 const UNUSED_IMPORT: &str = "UNUSED IMPORT";
+const FORBIDDEN_IMPORT: &str = "FORBIDDEN IMPORT";
+const DEPRECATED: &str = "DEPRECATED";
 const UNUSED_ALIAS_PARAM: &str = "UNUSED TYPE ALIAS PARAMETER";
 const UNBOUND_TYPE_VARIABLE: &str = "UNBOUND TYPE VARIABLE";
 const UNUSED_ARG: &str = "UNUSED ARGUMENT";
@@ -121,6 +123,54 @@ pub fn can_problem<'b>(
 
             title = UNUSED_IMPORT.to_string();
         }
+        Problem::ForbiddenImport {
+            imported,
+            region,
+            rule,
+        } => {
+            doc = alloc.stack([
+                alloc.concat([
+                    alloc.reflow("This module is not allowed to import "),
+                    alloc.module(imported),
+                    alloc.reflow(":"),
+                ]),
+                alloc.region(lines.convert_region(region)),
+                alloc.reflow("Your project's layering rules forbid this import:"),
+                alloc
+                    .string(rule)
+                    .annotate(Annotation::ParserSuggestion)
+                    .indent(4),
+                alloc.reflow("Can you get what you need from an allowed module instead?"),
+            ]);
+
+            title = FORBIDDEN_IMPORT.to_string();
+        }
+        Problem::Deprecated {
+            symbol,
+            region,
+            message,
+        } => {
+            let reason = if message.is_empty() {
+                alloc.reflow("It may be removed in a future version.")
+            } else {
+                alloc.stack([
+                    alloc.reflow("Its documentation says:"),
+                    alloc.string(message).indent(4),
+                ])
+            };
+
+            doc = alloc.stack([
+                alloc.concat([
+                    alloc.reflow("This uses "),
+                    alloc.symbol_qualified(symbol),
+                    alloc.reflow(", which is deprecated:"),
+                ]),
+                alloc.region(lines.convert_region(region)),
+                reason,
+            ]);
+
+            title = DEPRECATED.to_string();
+        }
         Problem::DefsOnlyUsedInRecursion(1, region) => {
             doc = alloc.stack([
                 alloc.reflow("This definition is only used in recursion with itself:"),
@@ -147,11 +197,12 @@ pub fn can_problem<'b>(
 
             title = "DEFINITIONs ONLY USED IN RECURSION".to_string();
         }
-        Problem::ExposedButNotDefined(symbol) => {
+        Problem::ExposedButNotDefined(symbol, region) => {
             doc = alloc.stack([
                 alloc.symbol_unqualified(symbol).append(
                     alloc.reflow(" is listed as exposed, but it isn't defined in this module."),
                 ),
+                alloc.region(lines.convert_region(region)),
                 alloc
                     .reflow("You can fix this by adding a definition for ")
                     .append(alloc.symbol_unqualified(symbol))
@@ -493,16 +544,51 @@ pub fn can_problem<'b>(
         Problem::SignatureDefMismatch {
             ref annotation_pattern,
             ref def_pattern,
+            annotation_name,
+            def_name,
         } => {
-            doc = alloc.stack([
-                alloc.reflow(
-                    "This annotation does not match the definition immediately following it:",
-                ),
-                alloc.region(
-                    lines.convert_region(Region::span_across(annotation_pattern, def_pattern)),
-                ),
-                alloc.reflow("Is it a typo? If not, put either a newline or comment between them."),
-            ]);
+            let snippet = alloc
+                .region(lines.convert_region(Region::span_across(annotation_pattern, def_pattern)));
+
+            doc = match (annotation_name, def_name) {
+                (Some(annotation_name), Some(def_name)) => {
+                    let annotation_str = annotation_name.as_str();
+                    let def_str = def_name.as_str();
+
+                    let explanation = if annotation_str.eq_ignore_ascii_case(def_str) {
+                        ", which only differs in capitalization. Is that a typo?"
+                    } else if distance::damerau_levenshtein(annotation_str, def_str)
+                        <= annotation_str.len() / 3
+                    {
+                        ", which is spelled almost the same. Is that a typo?"
+                    } else {
+                        ". If they don't belong together, put either a newline or comment between them."
+                    };
+
+                    alloc.stack([
+                        alloc.concat([
+                            alloc.reflow("The annotation for "),
+                            alloc.ident(annotation_name),
+                            alloc.reflow(" is not followed by its definition:"),
+                        ]),
+                        snippet,
+                        alloc.concat([
+                            alloc.reflow("The definition right after it is for "),
+                            alloc.ident(def_name),
+                            alloc.reflow(explanation),
+                        ]),
+                    ])
+                }
+                _ => alloc.stack([
+                    alloc.reflow(
+                        "This annotation does not match the definition immediately following it:",
+                    ),
+                    snippet,
+                    alloc.reflow(
+                        "Is it a typo? If not, put either a newline or comment between them.",
+                    ),
+                ]),
+            };
 
             title = NAMING_PROBLEM.to_string();
         }
@@ -1094,6 +1180,17 @@ pub fn can_problem<'b>(
             doc = report.doc;
             title = report.title;
         }
+        Problem::SyntaxError {
+            title: syntax_title,
+            message,
+            region: _,
+        } => {
+            doc = alloc.intersperse(
+                message.lines().map(|line| alloc.string(line.to_string())),
+                alloc.hardline(),
+            );
+            title = syntax_title;
+        }
     };
 
     Report {
@@ -1543,6 +1640,12 @@ fn report_shadowing<'b>(
 ) -> (&'static str, RocDocBuilder<'b>) {
     let (what, what_plural, is_builtin) = match kind {
         ShadowKind::Variable => ("variable", "variables", false),
+        ShadowKind::BuiltinImport(builtin) => {
+            let doc =
+                report_shadowed_builtin_import(alloc, lines, original_region, shadow, builtin);
+
+            return (DUPLICATE_NAME, doc);
+        }
         ShadowKind::Alias(sym) => ("alias", "aliases", sym.is_builtin()),
         ShadowKind::Opaque(sym) => ("opaque type", "opaque types", sym.is_builtin()),
         ShadowKind::Ability(sym) => ("ability", "abilities", sym.is_builtin()),
@@ -1584,6 +1687,34 @@ fn report_shadowing<'b>(
     (DUPLICATE_NAME, doc)
 }
 
+fn report_shadowed_builtin_import<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    original_region: Region,
+    shadow: Loc<Ident>,
+    builtin: Symbol,
+) -> RocDocBuilder<'b> {
+    alloc.stack([
+        alloc.concat([
+            alloc.reflow("The "),
+            alloc.ident(shadow.value.clone()),
+            alloc.reflow(" name is already used by the builtin "),
+            alloc.symbol_qualified(builtin),
+            alloc.reflow(", which this module imports here:"),
+        ]),
+        alloc.region(lines.convert_region(original_region)),
+        alloc.reflow("But then it's defined a second time here:"),
+        alloc.region(lines.convert_region(shadow.region)),
+        alloc.concat([
+            alloc.reflow("Give this one a different name, or remove "),
+            alloc.ident(shadow.value),
+            alloc.reflow(" from the import and write "),
+            alloc.symbol_qualified(builtin),
+            alloc.reflow(" where you mean the builtin."),
+        ]),
+    ])
+}
+
 fn pretty_runtime_error<'b>(
     alloc: &'b RocDocAllocator<'b>,
     lines: &LineInfo,
@@ -1615,6 +1746,8 @@ fn pretty_runtime_error<'b>(
         RuntimeError::LookupNotInScope {
             loc_name,
             suggestion_options: options,
+            qualified_suggestions,
+            import_candidate,
             underscored_suggestion_region,
         } => {
             doc = not_found(
@@ -1623,8 +1756,38 @@ fn pretty_runtime_error<'b>(
                 loc_name.region,
                 &loc_name.value,
                 options,
+                qualified_suggestions,
                 underscored_suggestion_region,
             );
+
+            if let Some(ImportCandidate { symbol, imported }) = import_candidate {
+                let module_name = symbol.module_string(alloc.interns).clone();
+
+                let how = if imported {
+                    alloc.concat([
+                        alloc.reflow(" exposes a value with this name. You can refer to it as "),
+                        alloc.symbol_qualified(symbol),
+                        alloc.reflow(", or expose it in your imports."),
+                    ])
+                } else {
+                    alloc.concat([
+                        alloc.reflow(" exposes a value with this name. Import "),
+                        alloc.module_name(module_name.clone()),
+                        alloc.reflow(" to refer to it as "),
+                        alloc.symbol_qualified(symbol),
+                        alloc.reflow("."),
+                    ])
+                };
+
+                doc = alloc.stack([
+                    doc,
+                    alloc
+                        .tip()
+                        .append(alloc.module_name(module_name))
+                        .append(how),
+                ]);
+            }
+
             title = UNRECOGNIZED_NAME;
         }
         RuntimeError::CircularDef(entries) => {
@@ -2262,12 +2425,20 @@ fn not_found<'b>(
     region: roc_region::all::Region,
     name: &Ident,
     options: MutSet<Box<str>>,
+    qualified_suggestions: Vec<Box<str>>,
     underscored_suggestion_region: Option<Region>,
 ) -> RocDocBuilder<'b> {
-    let mut suggestions = suggest::sort(
+    let unqualified = suggest::sort(
         name.as_inline_str().as_str(),
         options.iter().map(|v| v.as_ref()).collect(),
     );
+
+    // The qualified ones are only there if they are very close, so they go first
+    let mut suggestions: Vec<&str> = qualified_suggestions
+        .iter()
+        .map(|v| v.as_ref())
+        .chain(unqualified)
+        .collect();
     suggestions.truncate(4);
 
     let default_no = alloc.concat([
@@ -1,9 +1,10 @@
+use roc_module::symbol::{Interns, ModuleId};
 use roc_parse::parser::{ENumber, ESingleQuote, FileError, PList, SyntaxError};
 use roc_problem::Severity;
 use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Position, Region};
 use std::path::PathBuf;
 
-use crate::report::{Report, RocDocAllocator, RocDocBuilder};
+use crate::report::{Annotation, Report, RocDocAllocator, RocDocBuilder};
 use ven_pretty::DocAllocator;
 
 pub fn parse_problem<'a>(
@@ -16,6 +17,38 @@ pub fn parse_problem<'a>(
     to_syntax_report(alloc, lines, filename, &parse_problem.problem.problem)
 }
 
+/// A syntax error the parser recovered from, as a problem to report with the rest of its
+/// module's. `region` is the source the parser skipped over.
+pub fn recovered_syntax_problem(
+    home: ModuleId,
+    src: &str,
+    filename: PathBuf,
+    region: Region,
+    parse_problem: &SyntaxError<'_>,
+) -> roc_problem::can::Problem {
+    let src_lines: Vec<&str> = src.lines().collect();
+    let lines = LineInfo::new(src);
+    let interns = Interns::default();
+    let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+    let report = to_syntax_report(&alloc, &lines, filename, parse_problem);
+    let title = report.title.clone();
+
+    // Render only the body; the title goes in the header when the problem is reported
+    let mut message = String::new();
+    Report {
+        title: String::new(),
+        ..report
+    }
+    .render_ci(&mut message, &alloc);
+
+    roc_problem::can::Problem::SyntaxError {
+        title,
+        message,
+        region,
+    }
+}
+
 fn note_for_record_type_indent<'a>(alloc: &'a RocDocAllocator<'a>) -> RocDocBuilder<'a> {
     alloc.note("I may be confused by indentation")
 }
@@ -3264,23 +3297,51 @@ fn to_header_report<'a>(
         }
 
         EHeader::InconsistentModuleName(region) => {
-            let doc = alloc.stack([
+            let lc_region = lines.convert_region(*region);
+
+            let mut doc = vec![
                 alloc.reflow(
                     r"This module name does not correspond with the file path it is defined in:",
                 ),
-                alloc.region(lines.convert_region(*region)),
-                alloc.concat([
-                    alloc.reflow("Module names must correspond with the file paths they are defined in. For example, I expect to see "),
-                    alloc.parser_suggestion("BigNum"),
-                    alloc.reflow(" defined in "),
-                    alloc.parser_suggestion("BigNum.roc"),
-                    alloc.reflow(", or "),
-                    alloc.parser_suggestion("Math.Sin"),
-                    alloc.reflow(" defined in "),
-                    alloc.parser_suggestion("Math/Sin.roc"),
-                    alloc.reflow("."),
-                ]),
-            ]);
+                alloc.region(lc_region),
+            ];
+
+            // The region covers exactly the declared name, e.g. `Math.Sin`
+            let opt_declared_name =
+                alloc
+                    .src_lines
+                    .get(lc_region.start.line as usize)
+                    .and_then(|line| {
+                        line.get(lc_region.start.column as usize..lc_region.end.column as usize)
+                    });
+
+            if let Some(declared_name) = opt_declared_name {
+                doc.push(
+                    alloc.concat([
+                        alloc.reflow("Based on its name, I expect "),
+                        alloc.parser_suggestion(declared_name),
+                        alloc.reflow(" to be defined in "),
+                        alloc
+                            .string(format!("{}.roc", declared_name.replace('.', "/")))
+                            .annotate(Annotation::ParserSuggestion),
+                        alloc.reflow(", relative to the source directory."),
+                    ]),
+                );
+            }
+
+            doc.push(alloc.concat([
+                alloc.reflow("Module names must correspond with the file paths they are defined in. For example, I expect to see "),
+                alloc.parser_suggestion("BigNum"),
+                alloc.reflow(" defined in "),
+                alloc.parser_suggestion("BigNum.roc"),
+                alloc.reflow(", or "),
+                alloc.parser_suggestion("Math.Sin"),
+                alloc.reflow(" defined in "),
+                alloc.parser_suggestion("Math/Sin.roc"),
+                alloc.reflow("."),
+            ]));
+
+            let doc = alloc.stack(doc);
 
             Report {
                 filename,
@@ -1,7 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::error::canonicalize::{to_circular_def_doc, CIRCULAR_DEF};
-use crate::report::{Annotation, Report, RocDocAllocator, RocDocBuilder};
+use crate::report::{Annotation, CiWrite, Report, RocDocAllocator, RocDocBuilder};
 use itertools::EitherOrBoth;
 use itertools::Itertools;
 use roc_can::expected::{Expected, PExpected};
@@ -740,10 +740,13 @@ fn to_expr_report<'b>(
                     the_name_text,
                     alloc.text(" definition:"),
                 ]),
+                // The app provides a value whose type doesn't match the platform's `requires`.
+                // This is reported in the platform module, which the app author may never have
+                // opened, so say plainly whose side each type comes from.
                 RequiredSymbol { .. } => alloc.concat([
-                    alloc.text("type annotation of "),
-                    the_name_text,
-                    alloc.text(" required symbol:"),
+                    alloc.reflow("The app provides "),
+                    pattern_to_doc(alloc, &name.value).unwrap_or_else(|| alloc.text("a value")),
+                    alloc.reflow(", but not with the type this platform requires:"),
                 ]),
             };
 
@@ -790,20 +793,31 @@ fn to_expr_report<'b>(
                     expected_type,
                     expectation_context,
                     add_category(alloc, alloc.text(it_is), &category),
-                    alloc.concat([
-                        alloc.text("But the type annotation"),
-                        on_name_text,
-                        alloc.text(" says it should be:"),
-                    ]),
+                    match annotation_source {
+                        RequiredSymbol { .. } => alloc.text("But the platform requires it to be:"),
+                        _ => alloc.concat([
+                            alloc.text("But the type annotation"),
+                            on_name_text,
+                            alloc.text(" says it should be:"),
+                        ]),
+                    },
                     None,
                 )
             };
 
+            let (title, intro) = match annotation_source {
+                RequiredSymbol { .. } => ("PLATFORM REQUIREMENT MISMATCH", thing),
+                _ => (
+                    "TYPE MISMATCH",
+                    alloc.text("Something is off with the ").append(thing),
+                ),
+            };
+
             Report {
-                title: "TYPE MISMATCH".to_string(),
+                title: title.to_string(),
                 filename,
                 doc: alloc.stack([
-                    alloc.text("Something is off with the ").append(thing),
+                    intro,
                     {
                         // for typed bodies, include the line(s) with the signature
                         let joined =
@@ -5042,6 +5056,56 @@ pub fn unhandled_patterns_to_doc_block<'b>(
         .annotate(Annotation::TypeBlock)
 }
 
+/// Wide enough that a rendered pattern is never broken across lines.
+const ONE_LINE_WIDTH: usize = 1 << 16;
+
+/// If `problem` is a `when` that doesn't cover every pattern, returns the region of its branches
+/// and the missing patterns as Roc source, ready to be inserted as new branches. Patterns that
+/// can't be written as source (such as a branch that is only missing its guard) are left out.
+pub fn missing_when_branches<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    problem: &TypeError,
+) -> Option<(Region, Vec<String>)> {
+    use roc_exhaustive::Context::BadCase;
+    use roc_exhaustive::Error::Incomplete;
+
+    let TypeError::Exhaustive(Incomplete(region, BadCase, missing)) = problem else {
+        return None;
+    };
+
+    let patterns: Vec<String> = missing
+        .iter()
+        .filter(|pattern| !has_guard(pattern))
+        .map(|pattern| {
+            let mut buf = String::new();
+            exhaustive_pattern_to_doc(alloc, pattern.clone())
+                .1
+                .render_raw(ONE_LINE_WIDTH, &mut CiWrite::new(&mut buf))
+                .expect("<buffer is not a utf-8 encoded string>");
+            buf
+        })
+        .collect();
+
+    if patterns.is_empty() {
+        None
+    } else {
+        Some((*region, patterns))
+    }
+}
+
+fn has_guard(pattern: &roc_exhaustive::Pattern) -> bool {
+    use roc_exhaustive::Pattern::*;
+    use roc_exhaustive::RenderAs;
+
+    match pattern {
+        Anything | Literal(_) => false,
+        List(_, patterns) => patterns.iter().any(has_guard),
+        Ctor(union, _, args) => {
+            matches!(union.render_as, RenderAs::Guard) || args.iter().any(has_guard)
+        }
+    }
+}
+
 fn exhaustive_pattern_to_doc<'b>(
     alloc: &'b RocDocAllocator<'b>,
     pattern: roc_exhaustive::Pattern,
@@ -1,10 +1,13 @@
 use roc_module::ident::Ident;
 use roc_module::ident::{Lowercase, ModuleName, TagName, Uppercase};
 use roc_module::symbol::{Interns, ModuleId, ModuleIds, PQModuleName, PackageQualified, Symbol};
+use roc_parse::header::HeaderRole;
 use roc_problem::Severity;
 use roc_region::all::LineColumnRegion;
 use std::path::{Path, PathBuf};
 use std::{fmt, io};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 use ven_pretty::{text, BoxAllocator, DocAllocator, DocBuilder, Render, RenderAnnotated};
 
 #[cfg(not(target_family = "wasm"))]
@@ -13,8 +16,8 @@ use byte_unit::Byte;
 use roc_packaging::https::Problem;
 
 pub use crate::error::canonicalize::can_problem;
-pub use crate::error::parse::parse_problem;
-pub use crate::error::r#type::type_problem;
+pub use crate::error::parse::{parse_problem, recovered_syntax_problem};
+pub use crate::error::r#type::{missing_when_branches, type_problem};
 
 #[cfg(windows)]
 const CYCLE_ELEMENTS: [&str; 4] = ["+-----+", "|     ", "|     |", "+-<---+"];
@@ -603,28 +606,24 @@ impl<'a> RocDocAllocator<'a> {
         }
 
         if error_highlight_line {
+            let line = region.end().line;
+            let start1 = self.display_column(line, sub_region1.start().column);
+            let end1 = self.display_column(line, sub_region1.end().column);
+            let start2 = self.display_column(line, sub_region2.start().column);
+            let end2 = self.display_column(line, sub_region2.end().column);
+
             let overlapping = sub_region2.start().column < sub_region1.end().column;
 
             let highlight = if overlapping {
-                self.text(
-                    ERROR_UNDERLINE
-                        .repeat((sub_region2.end().column - sub_region1.start().column) as usize),
-                )
+                self.text(ERROR_UNDERLINE.repeat(end2.saturating_sub(start1)))
             } else {
-                let highlight1 = ERROR_UNDERLINE
-                    .repeat((sub_region1.end().column - sub_region1.start().column) as usize);
+                let highlight1 = ERROR_UNDERLINE.repeat(end1 - start1);
                 let highlight2 = if sub_region1 == sub_region2 {
                     "".repeat(0)
                 } else {
-                    ERROR_UNDERLINE
-                        .repeat((sub_region2.end().column - sub_region2.start().column) as usize)
+                    ERROR_UNDERLINE.repeat(end2 - start2)
                 };
-                let in_between = " ".repeat(
-                    (sub_region2
-                        .start()
-                        .column
-                        .saturating_sub(sub_region1.end().column)) as usize,
-                );
+                let in_between = " ".repeat(start2.saturating_sub(end1));
 
                 self.text(highlight1)
                     .append(self.text(in_between))
@@ -639,7 +638,7 @@ impl<'a> RocDocAllocator<'a> {
                 .append(if sub_region1.is_empty() && sub_region2.is_empty() {
                     self.nil()
                 } else {
-                    self.text(" ".repeat(sub_region1.start().column as usize))
+                    self.text(" ".repeat(start1))
                         .indent(indent)
                         .append(highlight)
                         .annotate(error_annotation)
@@ -736,8 +735,10 @@ impl<'a> RocDocAllocator<'a> {
         }
 
         if error_highlight_line {
-            let highlight_text = ERROR_UNDERLINE
-                .repeat((sub_region.end().column - sub_region.start().column) as usize);
+            let line = sub_region.start().line;
+            let start = self.display_column(line, sub_region.start().column);
+            let end = self.display_column(line, sub_region.end().column);
+            let highlight_text = ERROR_UNDERLINE.repeat(end.saturating_sub(start));
 
             let highlight_line = self
                 .line()
@@ -747,7 +748,7 @@ impl<'a> RocDocAllocator<'a> {
                 .append(if highlight_text.is_empty() {
                     self.nil()
                 } else {
-                    self.text(" ".repeat(sub_region.start().column as usize))
+                    self.text(" ".repeat(start))
                         .indent(indent)
                         .append(self.text(highlight_text).annotate(Annotation::Error))
                 });
@@ -758,6 +759,29 @@ impl<'a> RocDocAllocator<'a> {
         result
     }
 
+    /// How many columns the first `byte_column` bytes of a source line take up once the line is
+    /// printed in a snippet. Regions count bytes, but the carets under a snippet have to line up
+    /// with what is on screen, where `é` takes up one column, and `👋`, `👩‍🔬` or `中` take up
+    /// two. Control characters are dropped when the line is printed, so they don't count.
+    fn display_column(&self, line: u32, byte_column: u32) -> usize {
+        let src_line = self.src_lines.get(line as usize).copied().unwrap_or("");
+        let mut end = (byte_column as usize).min(src_line.len());
+
+        while !src_line.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        src_line[..end]
+            .graphemes(true)
+            .map(|grapheme| match grapheme {
+                "\t" => 1,
+                // terminals draw a cluster like 👩‍🔬 as one wide character,
+                // not as the sum of the characters it is made of
+                _ => grapheme.width().min(2),
+            })
+            .sum()
+    }
+
     pub fn region(&'a self, region: LineColumnRegion) -> DocBuilder<'a, Self, Annotation> {
         self.region_with_subregion(region, region)
     }
@@ -1583,6 +1607,18 @@ pub fn to_https_problem_report<'b>(
 }
 
 pub fn to_file_problem_report_string(filename: &Path, error: io::ErrorKind) -> String {
+    to_file_report_string(RenderTarget::ColorTerminal, &DEFAULT_PALETTE, |alloc| {
+        to_file_problem_report(alloc, filename, error)
+    })
+}
+
+/// Renders a report about a file as a whole, rather than about some code in it, so there are no
+/// source lines to show.
+pub fn to_file_report_string(
+    render: RenderTarget,
+    palette: &Palette,
+    to_report: impl for<'b> FnOnce(&'b RocDocAllocator<'b>) -> Report<'b>,
+) -> String {
     let src_lines: Vec<&str> = Vec::new();
 
     let mut module_ids = ModuleIds::default();
@@ -1591,17 +1627,235 @@ pub fn to_file_problem_report_string(filename: &Path, error: io::ErrorKind) -> S
 
     let interns = Interns::default();
 
-    // Report parsing and canonicalization problems
     let alloc = RocDocAllocator::new(&src_lines, module_id, &interns);
 
     let mut buf = String::new();
-    let palette = DEFAULT_PALETTE;
-    let report = to_file_problem_report(&alloc, filename, error);
-    report.render_color_terminal(&mut buf, &alloc, &palette);
+    to_report(&alloc).render(render, &mut buf, &alloc, palette);
 
     buf
 }
 
+pub fn to_file_too_large_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    filename: &Path,
+    size: u64,
+    limit: u64,
+) -> Report<'b> {
+    const MIB: u64 = 1024 * 1024;
+
+    let doc = alloc.stack([
+        alloc.reflow(r"This file is too large for me to load:"),
+        alloc
+            .string(filename.display().to_string())
+            .annotate(Annotation::Error)
+            .indent(4),
+        alloc.concat([
+            alloc.reflow("It is "),
+            alloc.string(if size < MIB {
+                format!("{size} bytes")
+            } else {
+                format!("{} MiB", (size + MIB - 1) / MIB)
+            }),
+            alloc.reflow(", but I can only load files of up to "),
+            alloc.string(if limit < MIB {
+                format!("{limit} bytes")
+            } else {
+                format!("{} MiB", limit / MIB)
+            }),
+            alloc.reflow("."),
+        ]),
+        alloc.reflow(
+            r"If this file is generated, can the generator split it into several smaller modules?",
+        ),
+    ]);
+
+    Report {
+        filename: "UNKNOWN.roc".into(),
+        doc,
+        title: "FILE TOO LARGE".to_string(),
+        severity: Severity::Fatal,
+    }
+}
+
+pub fn to_invalid_utf8_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    filename: &Path,
+    byte_offset: usize,
+    line: u32,
+    column: u32,
+) -> Report<'b> {
+    let doc = alloc.stack([
+        alloc.reflow(r"This file is not valid UTF-8:"),
+        alloc
+            .string(filename.display().to_string())
+            .annotate(Annotation::Error)
+            .indent(4),
+        alloc.concat([
+            alloc.reflow("The first invalid byte is at line "),
+            alloc.string(line.to_string()),
+            alloc.reflow(", column "),
+            alloc.string(column.to_string()),
+            alloc.reflow(" (byte offset "),
+            alloc.string(byte_offset.to_string()),
+            alloc.reflow(")."),
+        ]),
+        alloc.reflow(
+            r"Roc source files must be saved as UTF-8. Was this file saved in another encoding, like Latin-1 or UTF-16?",
+        ),
+    ]);
+
+    Report {
+        filename: "UNKNOWN.roc".into(),
+        doc,
+        title: "INVALID UTF-8".to_string(),
+        severity: Severity::Fatal,
+    }
+}
+
+pub fn to_ambiguous_module_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    module_name: &str,
+    candidates: &[PathBuf],
+) -> Report<'b> {
+    let doc = alloc.stack([
+        alloc.concat([
+            alloc.reflow("I found more than one file for the "),
+            alloc.string(module_name.to_string()),
+            alloc.reflow(" module:"),
+        ]),
+        alloc
+            .stack(candidates.iter().map(|path| {
+                alloc
+                    .string(path.display().to_string())
+                    .annotate(Annotation::Error)
+            }))
+            .indent(4),
+        alloc.concat([
+            alloc.reflow("Each of these is in a different source root listed in "),
+            alloc.string("roc-roots.txt".to_string()),
+            alloc.reflow(". Can you rename or remove all but one of them?"),
+        ]),
+    ]);
+
+    Report {
+        filename: "UNKNOWN.roc".into(),
+        doc,
+        title: "AMBIGUOUS MODULE".to_string(),
+        severity: Severity::Fatal,
+    }
+}
+
+pub fn to_filename_case_mismatch_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    expected: &Path,
+    found: &Path,
+) -> Report<'b> {
+    let doc = alloc.stack([
+        alloc.reflow(r"I was looking for this file:"),
+        alloc.string(expected.display().to_string()).indent(4),
+        alloc.reflow(r"but the file that is there is spelled differently:"),
+        alloc
+            .string(found.display().to_string())
+            .annotate(Annotation::Error)
+            .indent(4),
+        alloc.reflow(
+            r"Some file systems ignore case, so this can load on one machine and fail on another. The import has to match the file name exactly to work everywhere. Can you rename the file, or change the import to match?",
+        ),
+    ]);
+
+    Report {
+        filename: "UNKNOWN.roc".into(),
+        doc,
+        title: "FILENAME CASE MISMATCH".to_string(),
+        severity: Severity::Fatal,
+    }
+}
+
+pub fn to_module_not_found_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    module_name: &str,
+    searched_paths: &[PathBuf],
+    suggestions: &[PathBuf],
+) -> Report<'b> {
+    let paths = |paths: &[PathBuf], annotation| {
+        alloc
+            .stack(paths.iter().map(|path| {
+                alloc
+                    .string(path.display().to_string())
+                    .annotate(annotation)
+            }))
+            .indent(4)
+    };
+
+    let hint = if suggestions.is_empty() {
+        alloc.reflow("Is there a typo in the module name, or is the file supposed to be there?")
+    } else {
+        alloc.stack([
+            alloc.reflow("Did you mean one of these?"),
+            paths(suggestions, Annotation::ParserSuggestion),
+        ])
+    };
+
+    let doc = alloc.stack([
+        alloc.concat([
+            alloc.reflow("I could not find the file for the "),
+            alloc.string(module_name.to_string()),
+            alloc.reflow(" module. I looked here:"),
+        ]),
+        paths(searched_paths, Annotation::Error),
+        hint,
+    ]);
+
+    Report {
+        filename: "UNKNOWN.roc".into(),
+        doc,
+        title: "MODULE NOT FOUND".to_string(),
+        severity: Severity::Fatal,
+    }
+}
+
+pub fn to_imported_app_module_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    filename: &Path,
+    module_name: &str,
+    imported_by: &[(HeaderRole, PathBuf)],
+) -> Report<'b> {
+    let doc = alloc.stack([
+        alloc.concat([
+            alloc.reflow("The "),
+            alloc.string(module_name.to_string()),
+            alloc.reflow(" module is imported by:"),
+        ]),
+        alloc
+            .stack(imported_by.iter().map(|(role, path)| {
+                alloc.concat([
+                    alloc.keyword(role.keyword()),
+                    alloc.space(),
+                    alloc.string(path.display().to_string()),
+                ])
+            }))
+            .indent(4),
+        alloc.reflow("but its file has an app header:"),
+        alloc
+            .string(filename.display().to_string())
+            .annotate(Annotation::Error)
+            .indent(4),
+        alloc.reflow(
+            r"An app can only be the root module, so it cannot be imported. A platform gets the values an app provides through its requires instead.",
+        ),
+        alloc.reflow(
+            r"Can you move what you need into an interface module, and import that instead?",
+        ),
+    ]);
+
+    Report {
+        filename: "UNKNOWN.roc".into(),
+        doc,
+        title: "IMPORTED APP MODULE".to_string(),
+        severity: Severity::Fatal,
+    }
+}
+
 pub fn to_file_problem_report<'b>(
     alloc: &'b RocDocAllocator<'b>,
     filename: &Path,